@@ -5,10 +5,12 @@
 //!
 //! A mintable fungible token with role-based access control.
 //! Only designated minters (backend server) can mint tokens.
+//! The standard `Vft` service is exposed next to the `Line` admin/extension service.
 
 use sails_rs::prelude::*;
 mod services;
-pub use services::line_token::LineTokenService;
+pub use services::line_token::{AdminAction, LineTokenService, SignerKey};
+pub use services::vft::VftService;
 
 /// LINE Token Program
 pub struct LineTokenProgram(());
@@ -25,4 +27,9 @@ impl LineTokenProgram {
     pub fn line(&self) -> LineTokenService {
         LineTokenService::new()
     }
+
+    /// Get the standard VFT service
+    pub fn vft(&self) -> VftService {
+        VftService::new()
+    }
 }
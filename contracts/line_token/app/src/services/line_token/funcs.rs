@@ -34,9 +34,22 @@ pub fn mint(
     true
 }
 
+/// Get the part of an account's balance not locked by holds
+pub fn transferable_balance_of(
+    balances: &HashMap<ActorId, U256>,
+    held_balances: &HashMap<ActorId, U256>,
+    account: ActorId,
+) -> U256 {
+    balance_of(balances, account).saturating_sub(balance_of(held_balances, account))
+}
+
 /// Transfer tokens between accounts
+///
+/// Held funds are excluded from the sender's transferable balance.
+/// Panics if `from` and `to` are the same account.
 pub fn transfer(
     balances: &mut HashMap<ActorId, U256>,
+    held_balances: &HashMap<ActorId, U256>,
     from: ActorId,
     to: ActorId,
     value: U256,
@@ -45,11 +58,16 @@ pub fn transfer(
         return false;
     }
 
-    let from_balance = balance_of(balances, from);
-    if from_balance < value {
+    if from == to {
+        panic!("Cannot transfer to self");
+    }
+
+    if transferable_balance_of(balances, held_balances, from) < value {
         panic!("Insufficient balance");
     }
 
+    let from_balance = balance_of(balances, from);
+
     let new_from_balance = from_balance - value;
     let to_balance = balance_of(balances, to);
     let new_to_balance = to_balance
@@ -65,3 +83,170 @@ pub fn transfer(
 
     true
 }
+
+/// Get the allowance for a spender to spend from an owner
+pub fn allowance(
+    allowances: &HashMap<(ActorId, ActorId), U256>,
+    owner: ActorId,
+    spender: ActorId,
+) -> U256 {
+    allowances.get(&(owner, spender)).cloned().unwrap_or_default()
+}
+
+/// Decrease an allowance by `value`, removing it once exhausted
+pub fn spend_allowance(
+    allowances: &mut HashMap<(ActorId, ActorId), U256>,
+    owner: ActorId,
+    spender: ActorId,
+    value: U256,
+) {
+    let current_allowance = allowance(allowances, owner, spender);
+    if current_allowance < value {
+        panic!("Insufficient allowance");
+    }
+
+    let new_allowance = current_allowance - value;
+    if new_allowance.is_zero() {
+        allowances.remove(&(owner, spender));
+    } else {
+        allowances.insert((owner, spender), new_allowance);
+    }
+}
+
+/// Add `value` to a per-account cumulative counter
+pub fn accumulate(totals: &mut HashMap<ActorId, U256>, account: ActorId, value: U256) {
+    if value.is_zero() {
+        return;
+    }
+    let total = totals.entry(account).or_default();
+    *total = total.saturating_add(value);
+}
+
+/// Calculate a fee from a transfer value
+///
+/// # Arguments
+/// * `value` - The transferred amount
+/// * `fee_bps` - Fee in basis points (100 = 1%)
+pub fn calculate_fee(value: U256, fee_bps: u32) -> U256 {
+    if fee_bps == 0 {
+        return U256::zero();
+    }
+    // fee = value * bps / 10000
+    value
+        .checked_mul(U256::from(fee_bps))
+        .map(|product| product / U256::from(10000u32))
+        .unwrap_or_default()
+}
+
+/// Lock `amount` of an account's transferable balance
+pub fn hold(
+    balances: &HashMap<ActorId, U256>,
+    held_balances: &mut HashMap<ActorId, U256>,
+    account: ActorId,
+    amount: U256,
+) {
+    if transferable_balance_of(balances, held_balances, account) < amount {
+        panic!("Insufficient balance to hold");
+    }
+    accumulate(held_balances, account, amount);
+}
+
+/// Unlock `amount` previously held from an account
+pub fn release(held_balances: &mut HashMap<ActorId, U256>, account: ActorId, amount: U256) {
+    let held = balance_of(held_balances, account);
+    let remaining = held.checked_sub(amount).expect("Release exceeds held balance");
+    if remaining.is_zero() {
+        held_balances.remove(&account);
+    } else {
+        held_balances.insert(account, remaining);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_a() -> ActorId { ActorId::from(1u64) }
+    fn user_b() -> ActorId { ActorId::from(2u64) }
+
+    #[test]
+    fn test_fee_calculation() {
+        // 1000 LINE at 50 bps (0.5%) = 5 LINE
+        assert_eq!(calculate_fee(U256::from(1000u64), 50), U256::from(5u64));
+
+        // Rounds down for small values
+        assert_eq!(calculate_fee(U256::from(199u64), 50), U256::zero());
+
+        // 0 bps = no fee
+        assert_eq!(calculate_fee(U256::from(1000u64), 0), U256::zero());
+    }
+
+    #[test]
+    fn test_held_funds_excluded_from_transferable_balance() {
+        let mut balances = HashMap::new();
+        let mut held = HashMap::new();
+        balances.insert(user_a(), U256::from(100u64));
+
+        hold(&balances, &mut held, user_a(), U256::from(60u64));
+
+        assert_eq!(balance_of(&balances, user_a()), U256::from(100u64));
+        assert_eq!(transferable_balance_of(&balances, &held, user_a()), U256::from(40u64));
+    }
+
+    #[test]
+    fn test_transfer_of_unheld_funds_succeeds() {
+        let mut balances = HashMap::new();
+        let mut held = HashMap::new();
+        balances.insert(user_a(), U256::from(100u64));
+        hold(&balances, &mut held, user_a(), U256::from(60u64));
+
+        assert!(transfer(&mut balances, &held, user_a(), user_b(), U256::from(40u64)));
+        assert_eq!(balance_of(&balances, user_a()), U256::from(60u64));
+        assert_eq!(balance_of(&balances, user_b()), U256::from(40u64));
+    }
+
+    #[test]
+    #[should_panic(expected = "Insufficient balance")]
+    fn test_transfer_of_held_funds_panics() {
+        let mut balances = HashMap::new();
+        let mut held = HashMap::new();
+        balances.insert(user_a(), U256::from(100u64));
+        hold(&balances, &mut held, user_a(), U256::from(60u64));
+
+        transfer(&mut balances, &held, user_a(), user_b(), U256::from(41u64));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot transfer to self")]
+    fn test_transfer_to_self_panics() {
+        let mut balances = HashMap::new();
+        let held = HashMap::new();
+        balances.insert(user_a(), U256::from(100u64));
+
+        transfer(&mut balances, &held, user_a(), user_a(), U256::from(40u64));
+    }
+
+    #[test]
+    #[should_panic(expected = "Insufficient balance to hold")]
+    fn test_hold_cannot_exceed_transferable_balance() {
+        let mut balances = HashMap::new();
+        let mut held = HashMap::new();
+        balances.insert(user_a(), U256::from(100u64));
+        hold(&balances, &mut held, user_a(), U256::from(60u64));
+
+        hold(&balances, &mut held, user_a(), U256::from(50u64));
+    }
+
+    #[test]
+    fn test_release_restores_transferable_balance() {
+        let mut balances = HashMap::new();
+        let mut held = HashMap::new();
+        balances.insert(user_a(), U256::from(100u64));
+        hold(&balances, &mut held, user_a(), U256::from(60u64));
+
+        release(&mut held, user_a(), U256::from(60u64));
+
+        assert!(held.is_empty());
+        assert_eq!(transferable_balance_of(&balances, &held, user_a()), U256::from(100u64));
+    }
+}
@@ -10,22 +10,34 @@
 
 use sails_rs::{
     collections::{HashMap, HashSet},
-    gstd::{msg, service},
+    gstd::{msg, service, EventEmitter},
     prelude::*,
 };
 use gstd::exec;
 use blake2::{Blake2b, Digest};
 use blake2::digest::consts::U32;
-use schnorrkel::{PublicKey, Signature, signing_context};
 
-mod funcs;
+use super::vft;
+
+pub(crate) mod funcs;
+mod signer;
+
+pub use signer::SignerKey;
+
+/// SCALE-encoded `Line` service route, as exposed by `LineTokenProgram::line`
+const ROUTE: &[u8] = &[4 << 2, b'L', b'i', b'n', b'e'];
 
 /// Domain separator for withdrawal signatures
 const WITHDRAWAL_DOMAIN: &[u8] = b"LINE_WITHDRAW_V1";
 
-/// Signing context for schnorrkel (must match @polkadot/util-crypto)
-/// polkadot/util-crypto uses 'substrate' as the default signing context
-const SIGNING_CTX: &[u8] = b"substrate";
+/// Maximum transfer fee: 10% (1000 basis points)
+const MAX_TRANSFER_FEE_BPS: u32 = 1000;
+
+/// Maximum timelock delay: 30 days
+const MAX_TIMELOCK_DELAY_MS: u64 = 30 * 24 * 60 * 60 * 1000;
+
+/// Window after `eta_ms` during which a scheduled action can be executed: 14 days
+const TIMELOCK_GRACE_PERIOD_MS: u64 = 14 * 24 * 60 * 60 * 1000;
 
 /// Storage for LINE token
 #[derive(Default)]
@@ -42,8 +54,8 @@ pub struct Storage {
     pub admins: HashSet<ActorId>,
     
     // === Withdrawal feature fields ===
-    /// Backend signer public key (sr25519, 32 bytes)
-    pub backend_signer_pubkey: Option<[u8; 32]>,
+    /// Backend signer public key (sr25519, ed25519 or secp256k1)
+    pub backend_signer: Option<SignerKey>,
     /// Used withdrawal IDs to prevent replay attacks
     pub used_withdrawals: HashSet<[u8; 32]>,
     /// Emergency pause for withdrawals
@@ -54,14 +66,96 @@ pub struct Storage {
     // === Allowance feature fields (ERC20-style) ===
     /// Allowances: (owner, spender) -> approved amount
     pub allowances: HashMap<(ActorId, ActorId), U256>,
+
+    // === Rescue feature fields ===
+    /// Reject transfers addressed to the token program itself
+    pub reject_self_transfers: bool,
+
+    // === Statistics fields (for off-chain reconciliation) ===
+    /// Cumulative amount minted by each minter via `mint`
+    pub minted_by_minter: HashMap<ActorId, U256>,
+    /// Cumulative amount withdrawn by each user via `withdraw`
+    pub withdrawn_by_user: HashMap<ActorId, U256>,
+    /// Cumulative amount withdrawn by all users via `withdraw`
+    pub total_withdrawn: U256,
+    /// Number of executed withdrawals
+    pub withdrawal_count: u64,
+
+    // === Hold feature fields ===
+    /// Programs allowed to place holds on user balances
+    pub holders: HashSet<ActorId>,
+    /// Active holds: (holder, hold_id) -> hold
+    pub holds: HashMap<(ActorId, u64), Hold>,
+    /// Total held amount per account (excluded from transferable balance)
+    pub held_balances: HashMap<ActorId, U256>,
+
+    // === Transfer fee fields ===
+    /// Fee on peer-to-peer transfers in basis points (0 = disabled)
+    pub transfer_fee_bps: u32,
+    /// Account credited with transfer fees (no fee is charged while unset)
+    pub treasury: Option<ActorId>,
+    /// Accounts whose transfers are never charged a fee
+    pub fee_exempt: HashSet<ActorId>,
+
+    // === Timelock fields ===
+    /// Delay before a scheduled admin action can be executed (0 = no timelock)
+    pub timelock_delay_ms: u64,
+    /// Scheduled admin actions by ID
+    pub queued_actions: HashMap<u64, QueuedAction>,
+    /// Next scheduled action ID counter
+    pub next_action_id: u64,
+}
+
+/// Sensitive admin action subject to the timelock
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub enum AdminAction {
+    SetBackendSigner(SignerKey),
+    AddMinter(ActorId),
+    SetMaxWithdrawal(Option<U256>),
+    UnpauseWithdrawals,
+    SetTimelockDelay(u64),
+}
+
+/// Admin action waiting for its timelock delay to pass
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub struct QueuedAction {
+    /// Action to perform
+    pub action: AdminAction,
+    /// Admin who scheduled the action
+    pub proposer: ActorId,
+    /// Earliest execution timestamp (milliseconds since epoch)
+    pub eta_ms: u64,
+}
+
+/// Funds reserved on an account by a holder program
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub struct Hold {
+    /// Account whose balance is held
+    pub account: ActorId,
+    /// Held amount
+    pub amount: U256,
 }
 
 /// Token metadata
-#[derive(Default)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
 pub struct Metadata {
     pub name: String,
     pub symbol: String,
     pub decimals: u8,
+    /// Token icon URI (admin-updatable)
+    pub icon_uri: Option<String>,
+    /// Human-readable token description (admin-updatable)
+    pub description: Option<String>,
+    /// Project website URL (admin-updatable)
+    pub website: Option<String>,
 }
 
 static mut STORAGE: Option<Storage> = None;
@@ -108,7 +202,7 @@ pub enum Event {
     },
     /// Backend signer public key updated
     BackendSignerUpdated {
-        signer_pubkey: [u8; 32],
+        signer: SignerKey,
     },
     /// Withdrawals paused
     WithdrawalsPaused {},
@@ -120,6 +214,97 @@ pub enum Event {
         spender: ActorId,
         value: U256,
     },
+    /// Tokens held by the token program rescued by an admin
+    TokensRescued {
+        admin: ActorId,
+        to: ActorId,
+        amount: U256,
+    },
+    /// Guard against transfers to the token program toggled
+    SelfTransferGuardUpdated {
+        enabled: bool,
+    },
+    /// Extended token metadata updated
+    MetadataUpdated {
+        icon_uri: Option<String>,
+        description: Option<String>,
+        website: Option<String>,
+    },
+    /// Holder role granted
+    HolderAdded {
+        holder: ActorId,
+    },
+    /// Holder role revoked
+    HolderRemoved {
+        holder: ActorId,
+    },
+    /// Part of an account's balance held
+    FundsHeld {
+        holder: ActorId,
+        hold_id: u64,
+        account: ActorId,
+        amount: U256,
+    },
+    /// Held funds returned to the account's transferable balance
+    HoldReleased {
+        holder: ActorId,
+        hold_id: u64,
+        account: ActorId,
+        amount: U256,
+    },
+    /// Held funds moved to a recipient
+    HoldCaptured {
+        holder: ActorId,
+        hold_id: u64,
+        account: ActorId,
+        to: ActorId,
+        amount: U256,
+    },
+    /// Transfer fee deducted and credited to the treasury
+    FeeCharged {
+        from: ActorId,
+        treasury: ActorId,
+        fee: U256,
+    },
+    /// Transfer fee updated
+    TransferFeeUpdated {
+        bps: u32,
+    },
+    /// Treasury account updated
+    TreasuryUpdated {
+        treasury: Option<ActorId>,
+    },
+    /// Account exempted from transfer fees
+    FeeExemptAdded {
+        account: ActorId,
+    },
+    /// Fee exemption revoked
+    FeeExemptRemoved {
+        account: ActorId,
+    },
+    /// Admin action scheduled behind the timelock
+    ActionScheduled {
+        action_id: u64,
+        action: AdminAction,
+        eta_ms: u64,
+    },
+    /// Scheduled admin action cancelled
+    ActionCancelled {
+        action_id: u64,
+    },
+    /// Scheduled admin action executed
+    ActionExecuted {
+        action_id: u64,
+        action: AdminAction,
+    },
+    /// Scheduled admin action removed after its grace period ended
+    ActionExpired {
+        action_id: u64,
+    },
+    /// Timelock delay updated
+    TimelockDelayUpdated {
+        delay_ms: u64,
+    },
 }
 
 /// LINE Token Service
@@ -140,6 +325,7 @@ impl LineTokenService {
                     name,
                     symbol,
                     decimals,
+                    ..Default::default()
                 },
                 admins: [admin].into(),
                 minters: [admin].into(), // Deployer is initial minter
@@ -164,6 +350,8 @@ impl LineTokenService {
         let mutated = funcs::mint(&mut storage.balances, &mut storage.total_supply, to, value);
 
         if mutated {
+            funcs::accumulate(&mut storage.minted_by_minter, msg::source(), value);
+            vft::emit_transfer(ActorId::zero(), to, value);
             self.emit_event(Event::Minted { to, value })
                 .expect("Notification Error");
         }
@@ -174,23 +362,24 @@ impl LineTokenService {
     #[export]
     pub fn transfer(&mut self, to: ActorId, value: U256) -> bool {
         let from = msg::source();
-        let storage = Storage::get_mut();
-        let mutated = funcs::transfer(&mut storage.balances, from, to, value);
+        let Some(outcome) = transfer_tokens(from, to, value) else {
+            return false;
+        };
 
-        if mutated {
-            self.emit_event(Event::Transfer { from, to, value })
-                .expect("Notification Error");
+        for event in transfer_events(from, to, outcome) {
+            self.emit_event(event).expect("Notification Error");
         }
-        mutated
+        true
     }
 
     /// Add a minter (only admin)
+    /// Disabled while the timelock is active, use `schedule_action` instead.
     #[export]
     pub fn add_minter(&mut self, minter: ActorId) {
         self.ensure_admin();
-        Storage::get_mut().minters.insert(minter);
-        self.emit_event(Event::MinterAdded { minter })
-            .expect("Notification Error");
+        if let Some(event) = apply_without_timelock(AdminAction::AddMinter(minter)) {
+            self.emit_event(event).expect("Notification Error");
+        }
     }
 
     /// Remove a minter (only admin)
@@ -250,6 +439,43 @@ impl LineTokenService {
         Storage::get().admins.iter().cloned().collect()
     }
 
+    // =========================================================================
+    // METADATA FEATURE - Extended metadata for wallets and explorers
+    // =========================================================================
+
+    /// Set token icon URI (admin only, `None` clears it)
+    #[export]
+    pub fn set_icon_uri(&mut self, icon_uri: Option<String>) {
+        self.ensure_admin();
+        Storage::get_mut().meta.icon_uri = icon_uri;
+        self.emit_event(metadata_updated_event())
+            .expect("Notification Error");
+    }
+
+    /// Set token description (admin only, `None` clears it)
+    #[export]
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.ensure_admin();
+        Storage::get_mut().meta.description = description;
+        self.emit_event(metadata_updated_event())
+            .expect("Notification Error");
+    }
+
+    /// Set project website URL (admin only, `None` clears it)
+    #[export]
+    pub fn set_website(&mut self, website: Option<String>) {
+        self.ensure_admin();
+        Storage::get_mut().meta.website = website;
+        self.emit_event(metadata_updated_event())
+            .expect("Notification Error");
+    }
+
+    /// Get all token metadata in a single call
+    #[export]
+    pub fn metadata(&self) -> Metadata {
+        Storage::get().meta.clone()
+    }
+
     // =========================================================================
     // ALLOWANCE FEATURE - ERC20-style approve/transferFrom for marketplace
     // =========================================================================
@@ -259,10 +485,7 @@ impl LineTokenService {
     #[export]
     pub fn approve(&mut self, spender: ActorId, value: U256) -> bool {
         let owner = msg::source();
-        let storage = Storage::get_mut();
-        
-        // Store allowance (overwrites if exists)
-        storage.allowances.insert((owner, spender), value);
+        approve_tokens(owner, spender, value);
         
         self.emit_event(Event::Approval { owner, spender, value })
             .expect("Notification Error");
@@ -274,11 +497,7 @@ impl LineTokenService {
     /// Returns 0 if no allowance exists
     #[export]
     pub fn allowance(&self, owner: ActorId, spender: ActorId) -> U256 {
-        Storage::get()
-            .allowances
-            .get(&(owner, spender))
-            .cloned()
-            .unwrap_or_default()
+        funcs::allowance(&Storage::get().allowances, owner, spender)
     }
 
     /// Transfer tokens from one account to another using allowance
@@ -286,39 +505,70 @@ impl LineTokenService {
     #[export]
     pub fn transfer_from(&mut self, from: ActorId, to: ActorId, value: U256) -> bool {
         let caller = msg::source();
-        let storage = Storage::get_mut();
-        
-        // Get current allowance
-        let current_allowance = storage
-            .allowances
-            .get(&(from, caller))
-            .cloned()
-            .unwrap_or_default();
-        
-        // Check allowance
-        if current_allowance < value {
-            panic!("Insufficient allowance");
+        let Some(outcome) = transfer_tokens_from(caller, from, to, value) else {
+            return false;
+        };
+
+        for event in transfer_events(from, to, outcome) {
+            self.emit_event(event).expect("Notification Error");
         }
-        
-        // Decrease allowance FIRST (before transfer for reentrancy safety)
-        let new_allowance = current_allowance - value;
-        if new_allowance.is_zero() {
-            storage.allowances.remove(&(from, caller));
-        } else {
-            storage.allowances.insert((from, caller), new_allowance);
+        true
+    }
+
+    // =========================================================================
+    // RESCUE FEATURE - Recover tokens mistakenly sent to the token program
+    // =========================================================================
+
+    /// Move tokens held by the token program itself to `to` (admin only)
+    ///
+    /// Only the program's own balance can be rescued; user balances
+    /// are never touched.
+    #[export]
+    pub fn rescue(&mut self, to: ActorId, amount: U256) -> bool {
+        self.ensure_admin();
+
+        let program = exec::program_id();
+        if to == program {
+            panic!("Cannot rescue to the token program itself");
         }
-        
-        // Perform transfer using existing logic
-        let mutated = funcs::transfer(&mut storage.balances, from, to, value);
-        
+
+        let storage = Storage::get_mut();
+        let mutated = funcs::transfer(
+            &mut storage.balances,
+            &storage.held_balances,
+            program,
+            to,
+            amount,
+        );
+
         if mutated {
-            self.emit_event(Event::Transfer { from, to, value })
+            vft::emit_transfer(program, to, amount);
+            self.emit_event(Event::Transfer { from: program, to, value: amount })
                 .expect("Notification Error");
+            self.emit_event(Event::TokensRescued {
+                admin: msg::source(),
+                to,
+                amount,
+            }).expect("Notification Error");
         }
-        
         mutated
     }
 
+    /// Enable or disable rejection of transfers to the token program (admin only)
+    #[export]
+    pub fn set_reject_self_transfers(&mut self, enabled: bool) {
+        self.ensure_admin();
+        Storage::get_mut().reject_self_transfers = enabled;
+        self.emit_event(Event::SelfTransferGuardUpdated { enabled })
+            .expect("Notification Error");
+    }
+
+    /// Check if transfers to the token program are rejected
+    #[export]
+    pub fn reject_self_transfers(&self) -> bool {
+        Storage::get().reject_self_transfers
+    }
+
     // =========================================================================
     // WITHDRAWAL FEATURE - Backend-authorized, user-paid withdrawals
     // =========================================================================
@@ -329,7 +579,8 @@ impl LineTokenService {
     /// * `amount` - Amount of LINE tokens to withdraw (with decimals)
     /// * `withdrawal_id` - Unique 32-byte ID for this withdrawal (prevents replay)
     /// * `expiry` - Timestamp (ms) after which this withdrawal is invalid
-    /// * `signature` - 64-byte sr25519 signature from backend
+    /// * `signature` - Backend signature over the payload hash: 64 bytes for
+    ///   sr25519/ed25519, 65 bytes (r || s || v) for secp256k1
     #[export]
     pub fn withdraw(
         &mut self,
@@ -357,10 +608,13 @@ impl LineTokenService {
         }
 
         // 4. Get backend signer
-        let signer_pubkey = storage.backend_signer_pubkey
+        let signer = storage.backend_signer
             .expect("Backend signer not configured");
 
-        // 5. Check max withdrawal limit if set
+        // 5. Check amount is non-zero and within the max withdrawal limit if set
+        if amount.is_zero() {
+            panic!("Withdrawal amount must be greater than 0");
+        }
         if let Some(max) = storage.max_withdrawal_per_tx {
             if amount > max {
                 panic!("Amount exceeds maximum withdrawal limit");
@@ -371,17 +625,24 @@ impl LineTokenService {
         let caller = msg::source();
         let payload_hash = compute_withdrawal_hash(caller, amount, withdrawal_id, expiry);
 
-        // 7. Verify sr25519 signature
-        verify_sr25519_signature(&payload_hash, &signature, &signer_pubkey);
+        // 7. Verify signature with the configured signer scheme
+        signer::verify_signature(&signer, &payload_hash, &signature);
 
         // 8. Mark withdrawal_id as used (BEFORE minting to prevent reentrancy)
         let storage = Storage::get_mut();
         storage.used_withdrawals.insert(withdrawal_id);
 
         // 9. Mint tokens to caller
-        funcs::mint(&mut storage.balances, &mut storage.total_supply, caller, amount);
+        if funcs::mint(&mut storage.balances, &mut storage.total_supply, caller, amount) {
+            vft::emit_transfer(ActorId::zero(), caller, amount);
+        }
 
-        // 10. Emit event
+        // 10. Record withdrawal statistics
+        funcs::accumulate(&mut storage.withdrawn_by_user, caller, amount);
+        storage.total_withdrawn = storage.total_withdrawn.saturating_add(amount);
+        storage.withdrawal_count += 1;
+
+        // 11. Emit event
         self.emit_event(Event::WithdrawalExecuted {
             to: caller,
             amount,
@@ -392,12 +653,13 @@ impl LineTokenService {
     }
 
     /// Set backend signer public key (admin only)
+    /// Disabled while the timelock is active, use `schedule_action` instead.
     #[export]
-    pub fn set_backend_signer(&mut self, signer_pubkey: [u8; 32]) {
+    pub fn set_backend_signer(&mut self, signer: SignerKey) {
         self.ensure_admin();
-        Storage::get_mut().backend_signer_pubkey = Some(signer_pubkey);
-        self.emit_event(Event::BackendSignerUpdated { signer_pubkey })
-            .expect("Notification Error");
+        if let Some(event) = apply_without_timelock(AdminAction::SetBackendSigner(signer)) {
+            self.emit_event(event).expect("Notification Error");
+        }
     }
 
     /// Pause withdrawals (admin only, emergency stop)
@@ -410,19 +672,23 @@ impl LineTokenService {
     }
 
     /// Unpause withdrawals (admin only)
+    /// Disabled while the timelock is active, use `schedule_action` instead.
     #[export]
     pub fn unpause_withdrawals(&mut self) {
         self.ensure_admin();
-        Storage::get_mut().withdrawals_paused = false;
-        self.emit_event(Event::WithdrawalsUnpaused {})
-            .expect("Notification Error");
+        if let Some(event) = apply_without_timelock(AdminAction::UnpauseWithdrawals) {
+            self.emit_event(event).expect("Notification Error");
+        }
     }
 
     /// Set maximum withdrawal per transaction (admin only)
+    /// Disabled while the timelock is active, use `schedule_action` instead.
     #[export]
     pub fn set_max_withdrawal(&mut self, max_amount: Option<U256>) {
         self.ensure_admin();
-        Storage::get_mut().max_withdrawal_per_tx = max_amount;
+        if let Some(event) = apply_without_timelock(AdminAction::SetMaxWithdrawal(max_amount)) {
+            self.emit_event(event).expect("Notification Error");
+        }
     }
 
     /// Check if a withdrawal_id has been used
@@ -433,8 +699,8 @@ impl LineTokenService {
 
     /// Get backend signer public key
     #[export]
-    pub fn backend_signer(&self) -> Option<[u8; 32]> {
-        Storage::get().backend_signer_pubkey
+    pub fn backend_signer(&self) -> Option<SignerKey> {
+        Storage::get().backend_signer
     }
 
     /// Check if withdrawals are paused
@@ -448,6 +714,337 @@ impl LineTokenService {
     pub fn max_withdrawal(&self) -> Option<U256> {
         Storage::get().max_withdrawal_per_tx
     }
+
+    // =========================================================================
+    // HOLD FEATURE - Reserve user funds without moving them (wagers, bids)
+    // =========================================================================
+
+    /// Grant the holder role to a program (admin only)
+    #[export]
+    pub fn add_holder(&mut self, holder: ActorId) {
+        self.ensure_admin();
+        Storage::get_mut().holders.insert(holder);
+        self.emit_event(Event::HolderAdded { holder })
+            .expect("Notification Error");
+    }
+
+    /// Revoke the holder role (admin only)
+    ///
+    /// Existing holds stay in place and can still be released or captured.
+    #[export]
+    pub fn remove_holder(&mut self, holder: ActorId) {
+        self.ensure_admin();
+        Storage::get_mut().holders.remove(&holder);
+        self.emit_event(Event::HolderRemoved { holder })
+            .expect("Notification Error");
+    }
+
+    /// Hold part of an account's balance (holders only)
+    ///
+    /// Consumes `amount` of the allowance the account granted to the holder,
+    /// so users consent to holds the same way they consent to `transfer_from`.
+    /// Held funds stay in the account but cannot be transferred.
+    #[export]
+    pub fn hold(&mut self, account: ActorId, amount: U256, hold_id: u64) -> bool {
+        let holder = msg::source();
+        let storage = Storage::get_mut();
+        if !storage.holders.contains(&holder) {
+            panic!("Not holder: caller is not an authorized holder");
+        }
+        if amount.is_zero() {
+            panic!("Hold amount must be greater than 0");
+        }
+        if storage.holds.contains_key(&(holder, hold_id)) {
+            panic!("Hold ID already exists: {}", hold_id);
+        }
+
+        funcs::spend_allowance(&mut storage.allowances, account, holder, amount);
+        funcs::hold(&storage.balances, &mut storage.held_balances, account, amount);
+        storage.holds.insert((holder, hold_id), Hold { account, amount });
+
+        self.emit_event(Event::FundsHeld { holder, hold_id, account, amount })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Release a hold back to the account's transferable balance (holder only)
+    #[export]
+    pub fn release(&mut self, hold_id: u64) -> bool {
+        let holder = msg::source();
+        let storage = Storage::get_mut();
+        let Hold { account, amount } = storage.holds.remove(&(holder, hold_id))
+            .unwrap_or_else(|| panic!("Hold not found: {}", hold_id));
+
+        funcs::release(&mut storage.held_balances, account, amount);
+
+        self.emit_event(Event::HoldReleased { holder, hold_id, account, amount })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Capture held funds by moving them to `to` (holder only)
+    #[export]
+    pub fn capture(&mut self, hold_id: u64, to: ActorId) -> bool {
+        let holder = msg::source();
+        let storage = Storage::get_mut();
+        let Hold { account, amount } = storage.holds.remove(&(holder, hold_id))
+            .unwrap_or_else(|| panic!("Hold not found: {}", hold_id));
+
+        funcs::release(&mut storage.held_balances, account, amount);
+        funcs::transfer(&mut storage.balances, &storage.held_balances, account, to, amount);
+        vft::emit_transfer(account, to, amount);
+
+        self.emit_event(Event::Transfer { from: account, to, value: amount })
+            .expect("Notification Error");
+        self.emit_event(Event::HoldCaptured { holder, hold_id, account, to, amount })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Get an active hold placed by a holder
+    #[export]
+    pub fn get_hold(&self, holder: ActorId, hold_id: u64) -> Option<Hold> {
+        Storage::get().holds.get(&(holder, hold_id)).cloned()
+    }
+
+    /// Get the total held amount of an account
+    #[export]
+    pub fn held_balance_of(&self, account: ActorId) -> U256 {
+        funcs::balance_of(&Storage::get().held_balances, account)
+    }
+
+    /// Get the part of an account's balance that can be transferred
+    #[export]
+    pub fn transferable_balance_of(&self, account: ActorId) -> U256 {
+        let storage = Storage::get();
+        funcs::transferable_balance_of(&storage.balances, &storage.held_balances, account)
+    }
+
+    /// Check if an account is a holder
+    #[export]
+    pub fn is_holder(&self, account: ActorId) -> bool {
+        Storage::get().holders.contains(&account)
+    }
+
+    /// Get all holders
+    #[export]
+    pub fn holders(&self) -> Vec<ActorId> {
+        Storage::get().holders.iter().cloned().collect()
+    }
+
+    // =========================================================================
+    // TRANSFER FEE - Optional fee on peer-to-peer transfers for the treasury
+    // =========================================================================
+
+    /// Set the transfer fee in basis points (admin only)
+    #[export]
+    pub fn set_transfer_fee_bps(&mut self, bps: u32) {
+        self.ensure_admin();
+
+        if bps > MAX_TRANSFER_FEE_BPS {
+            panic!("Fee cannot exceed {} bps ({}%)", MAX_TRANSFER_FEE_BPS, MAX_TRANSFER_FEE_BPS / 100);
+        }
+
+        Storage::get_mut().transfer_fee_bps = bps;
+        self.emit_event(Event::TransferFeeUpdated { bps })
+            .expect("Notification Error");
+    }
+
+    /// Set the treasury account that receives transfer fees (admin only)
+    #[export]
+    pub fn set_treasury(&mut self, treasury: Option<ActorId>) {
+        self.ensure_admin();
+        Storage::get_mut().treasury = treasury;
+        self.emit_event(Event::TreasuryUpdated { treasury })
+            .expect("Notification Error");
+    }
+
+    /// Exempt an account (e.g. marketplace, minter) from transfer fees (admin only)
+    #[export]
+    pub fn add_fee_exempt(&mut self, account: ActorId) {
+        self.ensure_admin();
+        Storage::get_mut().fee_exempt.insert(account);
+        self.emit_event(Event::FeeExemptAdded { account })
+            .expect("Notification Error");
+    }
+
+    /// Revoke an account's fee exemption (admin only)
+    #[export]
+    pub fn remove_fee_exempt(&mut self, account: ActorId) {
+        self.ensure_admin();
+        Storage::get_mut().fee_exempt.remove(&account);
+        self.emit_event(Event::FeeExemptRemoved { account })
+            .expect("Notification Error");
+    }
+
+    /// Get the transfer fee in basis points
+    #[export]
+    pub fn transfer_fee_bps(&self) -> u32 {
+        Storage::get().transfer_fee_bps
+    }
+
+    /// Get the treasury account
+    #[export]
+    pub fn treasury(&self) -> Option<ActorId> {
+        Storage::get().treasury
+    }
+
+    /// Check if an account is exempt from transfer fees
+    #[export]
+    pub fn is_fee_exempt(&self, account: ActorId) -> bool {
+        Storage::get().fee_exempt.contains(&account)
+    }
+
+    /// Get all fee-exempt accounts
+    #[export]
+    pub fn fee_exempt_accounts(&self) -> Vec<ActorId> {
+        Storage::get().fee_exempt.iter().cloned().collect()
+    }
+
+    // =========================================================================
+    // TIMELOCK - Delayed execution of sensitive admin actions
+    // =========================================================================
+
+    /// Set the timelock delay (admin only)
+    ///
+    /// Only allowed while no timelock is active; once a delay is set,
+    /// changing it must itself go through `schedule_action`.
+    #[export]
+    pub fn set_timelock_delay(&mut self, delay_ms: u64) {
+        self.ensure_admin();
+        if let Some(event) = apply_without_timelock(AdminAction::SetTimelockDelay(delay_ms)) {
+            self.emit_event(event).expect("Notification Error");
+        }
+    }
+
+    /// Schedule a sensitive admin action (admin only)
+    ///
+    /// The action can be executed by anyone once the timelock delay has passed,
+    /// until the grace period after that expires.
+    #[export]
+    pub fn schedule_action(&mut self, action: AdminAction) -> u64 {
+        self.ensure_admin();
+        validate_admin_action(&action);
+
+        let storage = Storage::get_mut();
+        let eta_ms = exec::block_timestamp()
+            .checked_add(storage.timelock_delay_ms)
+            .expect("Execution time overflow");
+
+        let action_id = storage.next_action_id;
+        storage.next_action_id += 1;
+        storage.queued_actions.insert(action_id, QueuedAction {
+            action: action.clone(),
+            proposer: msg::source(),
+            eta_ms,
+        });
+
+        self.emit_event(Event::ActionScheduled { action_id, action, eta_ms })
+            .expect("Notification Error");
+
+        action_id
+    }
+
+    /// Cancel a scheduled admin action before it is executed (admin only)
+    #[export]
+    pub fn cancel_action(&mut self, action_id: u64) {
+        self.ensure_admin();
+
+        if Storage::get_mut().queued_actions.remove(&action_id).is_none() {
+            panic!("Action not found: {}", action_id);
+        }
+
+        self.emit_event(Event::ActionCancelled { action_id })
+            .expect("Notification Error");
+    }
+
+    /// Execute a scheduled admin action after its delay (permissionless)
+    ///
+    /// Actions not executed within the grace period after `eta_ms` expire
+    /// and must be scheduled again.
+    ///
+    /// # Returns
+    /// false if the action had expired; it is removed from the queue instead
+    #[export]
+    pub fn execute_action(&mut self, action_id: u64) -> bool {
+        let storage = Storage::get_mut();
+        let queued = storage.queued_actions.get(&action_id)
+            .unwrap_or_else(|| panic!("Action not found: {}", action_id));
+
+        let now = exec::block_timestamp();
+        if now < queued.eta_ms {
+            panic!("Timelock not expired: executable at {} ms", queued.eta_ms);
+        }
+        let expired = now > queued.eta_ms.saturating_add(TIMELOCK_GRACE_PERIOD_MS);
+
+        let action = storage.queued_actions.remove(&action_id).unwrap().action;
+        if expired {
+            self.emit_event(Event::ActionExpired { action_id })
+                .expect("Notification Error");
+            return false;
+        }
+        if let Some(event) = apply_admin_action(&action) {
+            self.emit_event(event).expect("Notification Error");
+        }
+
+        self.emit_event(Event::ActionExecuted { action_id, action })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Get the timelock delay in milliseconds
+    #[export]
+    pub fn timelock_delay(&self) -> u64 {
+        Storage::get().timelock_delay_ms
+    }
+
+    /// Get a scheduled admin action
+    #[export]
+    pub fn queued_action(&self, action_id: u64) -> Option<QueuedAction> {
+        Storage::get().queued_actions.get(&action_id).cloned()
+    }
+
+    /// Get all scheduled admin actions
+    #[export]
+    pub fn queued_actions(&self) -> Vec<(u64, QueuedAction)> {
+        Storage::get()
+            .queued_actions
+            .iter()
+            .map(|(id, queued)| (*id, queued.clone()))
+            .collect()
+    }
+
+    // =========================================================================
+    // STATISTICS - Cumulative mint/withdraw counters for reconciliation
+    // =========================================================================
+
+    /// Get total amount minted by a minter via `mint`
+    #[export]
+    pub fn minted_by(&self, minter: ActorId) -> U256 {
+        funcs::balance_of(&Storage::get().minted_by_minter, minter)
+    }
+
+    /// Get total amount withdrawn by a user via `withdraw`
+    #[export]
+    pub fn withdrawn_by(&self, user: ActorId) -> U256 {
+        funcs::balance_of(&Storage::get().withdrawn_by_user, user)
+    }
+
+    /// Get total amount withdrawn by all users via `withdraw`
+    #[export]
+    pub fn total_withdrawn(&self) -> U256 {
+        Storage::get().total_withdrawn
+    }
+
+    /// Get number of executed withdrawals
+    #[export]
+    pub fn withdrawal_count(&self) -> u64 {
+        Storage::get().withdrawal_count
+    }
 }
 
 impl LineTokenService {
@@ -458,6 +1055,147 @@ impl LineTokenService {
     }
 }
 
+/// Panic if an admin action has invalid parameters
+fn validate_admin_action(action: &AdminAction) {
+    if let AdminAction::SetTimelockDelay(delay_ms) = action {
+        if *delay_ms > MAX_TIMELOCK_DELAY_MS {
+            panic!("Timelock delay cannot exceed {} ms", MAX_TIMELOCK_DELAY_MS);
+        }
+    }
+}
+
+/// Apply an admin action directly, only allowed while no timelock is active
+fn apply_without_timelock(action: AdminAction) -> Option<Event> {
+    if Storage::get().timelock_delay_ms > 0 {
+        panic!("Action is timelocked: use schedule_action");
+    }
+    validate_admin_action(&action);
+    apply_admin_action(&action)
+}
+
+/// Apply an admin action to storage, returning the event to emit
+fn apply_admin_action(action: &AdminAction) -> Option<Event> {
+    let storage = Storage::get_mut();
+    match *action {
+        AdminAction::SetBackendSigner(signer) => {
+            storage.backend_signer = Some(signer);
+            Some(Event::BackendSignerUpdated { signer })
+        }
+        AdminAction::AddMinter(minter) => {
+            storage.minters.insert(minter);
+            Some(Event::MinterAdded { minter })
+        }
+        AdminAction::SetMaxWithdrawal(max_amount) => {
+            storage.max_withdrawal_per_tx = max_amount;
+            None
+        }
+        AdminAction::UnpauseWithdrawals => {
+            storage.withdrawals_paused = false;
+            Some(Event::WithdrawalsUnpaused {})
+        }
+        AdminAction::SetTimelockDelay(delay_ms) => {
+            storage.timelock_delay_ms = delay_ms;
+            Some(Event::TimelockDelayUpdated { delay_ms })
+        }
+    }
+}
+
+/// Build a `MetadataUpdated` event from the current extended metadata
+fn metadata_updated_event() -> Event {
+    let meta = &Storage::get().meta;
+    Event::MetadataUpdated {
+        icon_uri: meta.icon_uri.clone(),
+        description: meta.description.clone(),
+        website: meta.website.clone(),
+    }
+}
+
+/// Result of a fee-aware transfer
+pub(crate) struct TransferOutcome {
+    /// Amount credited to the recipient
+    pub received: U256,
+    /// Fee credited to the treasury, if one was charged
+    pub fee: Option<(ActorId, U256)>,
+}
+
+/// Move tokens between accounts, applying transfer guards and the transfer fee
+///
+/// Shared by the `Line` and `Vft` services. Emits the standard `Vft`
+/// transfer events and, for a charged fee, `FeeCharged`; callers emit their
+/// own transfer events.
+/// Returns `None` if nothing was moved (zero value).
+pub(crate) fn transfer_tokens(from: ActorId, to: ActorId, value: U256) -> Option<TransferOutcome> {
+    ensure_not_self_transfer(to);
+
+    let storage = Storage::get_mut();
+    // Charge a fee unless either side is the treasury or fee-exempt
+    let fee = match storage.treasury {
+        Some(treasury)
+            if ![from, to]
+                .iter()
+                .any(|account| *account == treasury || storage.fee_exempt.contains(account)) =>
+        {
+            let fee = funcs::calculate_fee(value, storage.transfer_fee_bps);
+            (!fee.is_zero()).then_some((treasury, fee))
+        }
+        _ => None,
+    };
+
+    let received = value - fee.map(|(_, fee)| fee).unwrap_or_default();
+    if !funcs::transfer(&mut storage.balances, &storage.held_balances, from, to, received) {
+        return None;
+    }
+    vft::emit_transfer(from, to, received);
+    if let Some((treasury, fee)) = fee {
+        funcs::transfer(&mut storage.balances, &storage.held_balances, from, treasury, fee);
+        vft::emit_transfer(from, treasury, fee);
+        EventEmitter::<Event>::new(ROUTE)
+            .emit_event(Event::FeeCharged { from, treasury, fee })
+            .expect("Notification Error");
+    }
+
+    Some(TransferOutcome { received, fee })
+}
+
+/// Build the events for a completed transfer: the transfer itself plus,
+/// if a fee was charged, the fee transfer (`FeeCharged` is emitted by
+/// `transfer_tokens`)
+fn transfer_events(from: ActorId, to: ActorId, outcome: TransferOutcome) -> Vec<Event> {
+    let mut events = vec![Event::Transfer { from, to, value: outcome.received }];
+    if let Some((treasury, fee)) = outcome.fee {
+        events.push(Event::Transfer { from, to: treasury, value: fee });
+    }
+    events
+}
+
+/// Spend `spender`'s allowance over `from` and move tokens to `to`
+pub(crate) fn transfer_tokens_from(
+    spender: ActorId,
+    from: ActorId,
+    to: ActorId,
+    value: U256,
+) -> Option<TransferOutcome> {
+    // Decrease allowance FIRST (before transfer for reentrancy safety)
+    let storage = Storage::get_mut();
+    funcs::spend_allowance(&mut storage.allowances, from, spender, value);
+
+    transfer_tokens(from, to, value)
+}
+
+/// Set `spender`'s allowance over `owner` (overwrites if exists)
+/// and emit the standard `Vft` approval event
+pub(crate) fn approve_tokens(owner: ActorId, spender: ActorId, value: U256) {
+    Storage::get_mut().allowances.insert((owner, spender), value);
+    vft::emit_approval(owner, spender, value);
+}
+
+/// Panic if the self-transfer guard is on and `to` is the token program
+fn ensure_not_self_transfer(to: ActorId) {
+    if Storage::get().reject_self_transfers && to == exec::program_id() {
+        panic!("Transfers to the token program are not allowed");
+    }
+}
+
 /// Compute blake2b-256 hash of withdrawal payload
 fn compute_withdrawal_hash(
     caller: ActorId,
@@ -490,31 +1228,3 @@ fn compute_withdrawal_hash(
     
     hasher.finalize().into()
 }
-
-/// Verify sr25519 signature using schnorrkel
-fn verify_sr25519_signature(
-    message_hash: &[u8; 32],
-    signature_bytes: &[u8],
-    pubkey_bytes: &[u8; 32],
-) {
-    // Parse signature (must be 64 bytes)
-    if signature_bytes.len() != 64 {
-        panic!("Invalid signature length: expected 64 bytes");
-    }
-    let mut sig_array = [0u8; 64];
-    sig_array.copy_from_slice(signature_bytes);
-    
-    let signature = Signature::from_bytes(&sig_array)
-        .expect("Invalid signature format");
-    
-    // Parse public key
-    let public_key = PublicKey::from_bytes(pubkey_bytes)
-        .expect("Invalid public key format");
-    
-    // Create signing context and verify
-    let ctx = signing_context(SIGNING_CTX);
-    
-    if public_key.verify(ctx.bytes(message_hash), &signature).is_err() {
-        panic!("Invalid signature: verification failed");
-    }
-}
@@ -0,0 +1,229 @@
+//! Backend signer keys and withdrawal signature verification
+//!
+//! Every scheme signs the same 32-byte blake2b-256 payload hash produced by
+//! `compute_withdrawal_hash`:
+//! - Sr25519: schnorrkel signature under the "substrate" signing context
+//! - Ed25519: plain ed25519 signature over the hash bytes
+//! - Secp256k1: recoverable ECDSA signature with the hash used as prehash
+
+use sails_rs::prelude::*;
+use schnorrkel::signing_context;
+
+/// Signing context for schnorrkel (must match @polkadot/util-crypto)
+/// polkadot/util-crypto uses 'substrate' as the default signing context
+const SIGNING_CTX: &[u8] = b"substrate";
+
+/// Backend signer public key, tagged by signature scheme
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub enum SignerKey {
+    /// sr25519 public key (32 bytes)
+    Sr25519([u8; 32]),
+    /// ed25519 public key (32 bytes)
+    Ed25519([u8; 32]),
+    /// secp256k1 compressed public key (33 bytes)
+    Secp256k1([u8; 33]),
+}
+
+/// Verify a withdrawal signature against the configured signer
+pub fn verify_signature(signer: &SignerKey, message_hash: &[u8; 32], signature_bytes: &[u8]) {
+    match signer {
+        SignerKey::Sr25519(pubkey) => verify_sr25519_signature(message_hash, signature_bytes, pubkey),
+        SignerKey::Ed25519(pubkey) => verify_ed25519_signature(message_hash, signature_bytes, pubkey),
+        SignerKey::Secp256k1(pubkey) => {
+            verify_secp256k1_signature(message_hash, signature_bytes, pubkey)
+        }
+    }
+}
+
+/// Verify sr25519 signature using schnorrkel
+fn verify_sr25519_signature(
+    message_hash: &[u8; 32],
+    signature_bytes: &[u8],
+    pubkey_bytes: &[u8; 32],
+) {
+    // Parse signature (must be 64 bytes)
+    if signature_bytes.len() != 64 {
+        panic!("Invalid signature length: expected 64 bytes");
+    }
+    let mut sig_array = [0u8; 64];
+    sig_array.copy_from_slice(signature_bytes);
+
+    let signature = schnorrkel::Signature::from_bytes(&sig_array)
+        .expect("Invalid signature format");
+
+    // Parse public key
+    let public_key = schnorrkel::PublicKey::from_bytes(pubkey_bytes)
+        .expect("Invalid public key format");
+
+    // Create signing context and verify
+    let ctx = signing_context(SIGNING_CTX);
+
+    if public_key.verify(ctx.bytes(message_hash), &signature).is_err() {
+        panic!("Invalid signature: verification failed");
+    }
+}
+
+/// Verify ed25519 signature over the raw payload hash
+fn verify_ed25519_signature(
+    message_hash: &[u8; 32],
+    signature_bytes: &[u8],
+    pubkey_bytes: &[u8; 32],
+) {
+    if signature_bytes.len() != 64 {
+        panic!("Invalid signature length: expected 64 bytes");
+    }
+    let mut sig_array = [0u8; 64];
+    sig_array.copy_from_slice(signature_bytes);
+
+    let signature = ed25519_dalek::Signature::from_bytes(&sig_array);
+
+    let public_key = ed25519_dalek::VerifyingKey::from_bytes(pubkey_bytes)
+        .expect("Invalid public key format");
+
+    if public_key.verify_strict(message_hash, &signature).is_err() {
+        panic!("Invalid signature: verification failed");
+    }
+}
+
+/// Verify recoverable secp256k1 signature (r || s || v) over the payload hash
+///
+/// The recovery byte may be 0/1 or Ethereum-style 27/28. High-S signatures
+/// (as returned by some KMS providers) are normalized before recovery.
+fn verify_secp256k1_signature(
+    message_hash: &[u8; 32],
+    signature_bytes: &[u8],
+    pubkey_bytes: &[u8; 33],
+) {
+    use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+    if signature_bytes.len() != 65 {
+        panic!("Invalid signature length: expected 65 bytes");
+    }
+
+    let v = match signature_bytes[64] {
+        v @ (0 | 1) => v,
+        v @ (27 | 28) => v - 27,
+        _ => panic!("Invalid signature format"),
+    };
+
+    let mut signature = Signature::from_slice(&signature_bytes[..64])
+        .expect("Invalid signature format");
+    let mut recovery_id = RecoveryId::from_byte(v).expect("Invalid signature format");
+
+    // Normalizing S negates the nonce point, which flips the y parity
+    if let Some(normalized) = signature.normalize_s() {
+        signature = normalized;
+        recovery_id = RecoveryId::new(!recovery_id.is_y_odd(), recovery_id.is_x_reduced());
+    }
+
+    let recovered = VerifyingKey::recover_from_prehash(message_hash, &signature, recovery_id)
+        .unwrap_or_else(|_| panic!("Invalid signature: verification failed"));
+
+    if recovered.to_encoded_point(true).as_bytes() != pubkey_bytes.as_slice() {
+        panic!("Invalid signature: verification failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::line_token::compute_withdrawal_hash;
+
+    // Shared payload: caller 0x11.., 1 LINE (9 decimals), id 0x22.., expiry 1_700_000_000_000
+    const PAYLOAD_HASH: &str = "cc4e55eb0f37c6cf65e70a8ca0d48b68dc8cb106330438c4036d915707cfd80a";
+
+    // sr25519 keypair from mini secret [0x01; 32]
+    const SR25519_PUBKEY: &str = "189dac29296d31814dc8c56cf3d36a0543372bba7538fa322a4aebfebc39e056";
+    const SR25519_SIG: &str = "fcff9267bebfc8a53137d65718a5db5866a3ed87786da88d95bd5d9cf4715606e0472da765df65b262eb54099d36a7cdd9ca5978bd8d2959c15220b65836de83";
+
+    // ed25519 keypair from secret [0x02; 32]
+    const ED25519_PUBKEY: &str = "8139770ea87d175f56a35466c34c7ecccb8d8a91b4ee37a25df60f5b8fc9b394";
+    const ED25519_SIG: &str = "151169ca9c4d01cb3872bb997058e829a31882c3ebac8aa2c998b9549f3f6568c57d44b2b7a999fc0d3d1f4cb584eb92ba8a666ac331162c0790875885ebec04";
+
+    // secp256k1 keypair from secret [0x03; 32]
+    const SECP256K1_PUBKEY: &str = "02531fe6068134503d2723133227c867ac8fa6c83c537e9a44c3c5bdbdcb1fe337";
+    const SECP256K1_SIG: &str = "2aa552c7159539e17e1b13abab7b83c55fb6193dc8e4751b0a6eaeb65af481ad28227ced2c2fdb7861c3d311e9e7e4590411948e5ed95059e6661e54fa44342100";
+    // Same signature with S negated (high-S) and an Ethereum-style recovery byte
+    const SECP256K1_SIG_HIGH_S: &str = "2aa552c7159539e17e1b13abab7b83c55fb6193dc8e4751b0a6eaeb65af481add7dd8312d3d024879e3c2cee16181ba5b69d4858506f4fe1d96c4037d5f20d201c";
+
+    fn hex<const N: usize>(s: &str) -> [u8; N] {
+        let mut out = [0u8; N];
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).unwrap();
+        }
+        out
+    }
+
+    fn payload_hash() -> [u8; 32] {
+        compute_withdrawal_hash(
+            ActorId::from([0x11u8; 32]),
+            U256::from(1_000_000_000u64),
+            [0x22u8; 32],
+            1_700_000_000_000,
+        )
+    }
+
+    #[test]
+    fn test_payload_hash_matches_vector() {
+        assert_eq!(payload_hash(), hex::<32>(PAYLOAD_HASH));
+    }
+
+    #[test]
+    fn test_sr25519_vector_verifies() {
+        let signer = SignerKey::Sr25519(hex(SR25519_PUBKEY));
+        verify_signature(&signer, &payload_hash(), &hex::<64>(SR25519_SIG));
+    }
+
+    #[test]
+    fn test_ed25519_vector_verifies() {
+        let signer = SignerKey::Ed25519(hex(ED25519_PUBKEY));
+        verify_signature(&signer, &payload_hash(), &hex::<64>(ED25519_SIG));
+    }
+
+    #[test]
+    fn test_secp256k1_vector_verifies() {
+        let signer = SignerKey::Secp256k1(hex(SECP256K1_PUBKEY));
+        verify_signature(&signer, &payload_hash(), &hex::<65>(SECP256K1_SIG));
+    }
+
+    #[test]
+    fn test_secp256k1_high_s_vector_verifies() {
+        let signer = SignerKey::Secp256k1(hex(SECP256K1_PUBKEY));
+        verify_signature(&signer, &payload_hash(), &hex::<65>(SECP256K1_SIG_HIGH_S));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid signature")]
+    fn test_ed25519_rejects_tampered_payload() {
+        let signer = SignerKey::Ed25519(hex(ED25519_PUBKEY));
+        let mut hash = payload_hash();
+        hash[0] ^= 1;
+        verify_signature(&signer, &hash, &hex::<64>(ED25519_SIG));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid signature")]
+    fn test_secp256k1_rejects_tampered_payload() {
+        let signer = SignerKey::Secp256k1(hex(SECP256K1_PUBKEY));
+        let mut hash = payload_hash();
+        hash[0] ^= 1;
+        verify_signature(&signer, &hash, &hex::<65>(SECP256K1_SIG));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid signature")]
+    fn test_signature_rejected_under_wrong_scheme() {
+        // ed25519 signature presented while the sr25519 key is configured
+        let signer = SignerKey::Sr25519(hex(SR25519_PUBKEY));
+        verify_signature(&signer, &payload_hash(), &hex::<64>(ED25519_SIG));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid signature length")]
+    fn test_secp256k1_rejects_short_signature() {
+        let signer = SignerKey::Secp256k1(hex(SECP256K1_PUBKEY));
+        verify_signature(&signer, &payload_hash(), &hex::<64>(SECP256K1_SIG));
+    }
+}
@@ -1 +1,2 @@
 pub mod line_token;
+pub mod vft;
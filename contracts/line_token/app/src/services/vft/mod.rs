@@ -0,0 +1,120 @@
+//! VFT Service - Standard Vara Fungible Token interface
+//!
+//! Exposes the standard `Vft` service (route names, method set and events)
+//! over the same storage as `LineTokenService`, so generic Vara wallets,
+//! DEXes and indexers can use LINE without a custom IDL.
+//! Minting, withdrawals and other admin functions stay on the `Line` service.
+//! Standard events are emitted from every path that changes balances or
+//! allowances, including `Line` methods, so VFT indexers see the full history.
+
+use sails_rs::{
+    gstd::{msg, service, EventEmitter},
+    prelude::*,
+};
+
+use super::line_token::{self, funcs, Storage};
+
+/// SCALE-encoded `Vft` service route, as exposed by `LineTokenProgram::vft`
+const ROUTE: &[u8] = &[3 << 2, b'V', b'f', b't'];
+
+/// Standard VFT events
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub enum Event {
+    /// Allowance set by an owner for a spender
+    Approval {
+        owner: ActorId,
+        spender: ActorId,
+        value: U256,
+    },
+    /// Tokens transferred
+    Transfer {
+        from: ActorId,
+        to: ActorId,
+        value: U256,
+    },
+}
+
+/// Standard VFT Service
+#[derive(Clone)]
+pub struct VftService;
+
+impl VftService {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[service(events = Event)]
+impl VftService {
+    /// Approve a spender to spend tokens on behalf of the caller
+    #[export]
+    pub fn approve(&mut self, spender: ActorId, value: U256) -> bool {
+        line_token::approve_tokens(msg::source(), spender, value);
+        true
+    }
+
+    /// Transfer tokens to another account
+    #[export]
+    pub fn transfer(&mut self, to: ActorId, value: U256) -> bool {
+        line_token::transfer_tokens(msg::source(), to, value).is_some()
+    }
+
+    /// Transfer tokens from one account to another using allowance
+    #[export]
+    pub fn transfer_from(&mut self, from: ActorId, to: ActorId, value: U256) -> bool {
+        line_token::transfer_tokens_from(msg::source(), from, to, value).is_some()
+    }
+
+    /// Get the allowance for a spender to spend from an owner
+    #[export]
+    pub fn allowance(&self, owner: ActorId, spender: ActorId) -> U256 {
+        funcs::allowance(&Storage::get().allowances, owner, spender)
+    }
+
+    /// Get the balance of an account
+    #[export]
+    pub fn balance_of(&self, account: ActorId) -> U256 {
+        funcs::balance_of(&Storage::get().balances, account)
+    }
+
+    /// Get token decimals
+    #[export]
+    pub fn decimals(&self) -> u8 {
+        Storage::get().meta.decimals
+    }
+
+    /// Get token name
+    #[export]
+    pub fn name(&self) -> String {
+        Storage::get().meta.name.clone()
+    }
+
+    /// Get token symbol
+    #[export]
+    pub fn symbol(&self) -> String {
+        Storage::get().meta.symbol.clone()
+    }
+
+    /// Get total supply
+    #[export]
+    pub fn total_supply(&self) -> U256 {
+        Storage::get().total_supply
+    }
+}
+
+/// Emit a standard `Transfer` event (`from` is zero on mint)
+pub(crate) fn emit_transfer(from: ActorId, to: ActorId, value: U256) {
+    EventEmitter::<Event>::new(ROUTE)
+        .emit_event(Event::Transfer { from, to, value })
+        .expect("Notification Error");
+}
+
+/// Emit a standard `Approval` event
+pub(crate) fn emit_approval(owner: ActorId, spender: ActorId, value: U256) {
+    EventEmitter::<Event>::new(ROUTE)
+        .emit_event(Event::Approval { owner, spender, value })
+        .expect("Notification Error");
+}
@@ -41,7 +41,7 @@ export function getBackendKeypair() {
 }
 
 /**
- * Get backend public key (to register in contract via set_backend_signer)
+ * Get backend public key (to register in contract via set_backend_signer({ Sr25519: pubkey }))
  */
 export function getBackendPublicKey(): string {
     const keypair = getBackendKeypair();
@@ -3,16 +3,20 @@
 
 //! NFT Contract - Marketplace-controlled Non-Fungible Token
 //!
-//! A minimal NFT implementation where only admins (marketplace) can:
-//! - Mint new tokens
-//! - Transfer tokens between accounts
+//! An NFT implementation where:
+//! - Only admins (marketplace) can mint new tokens
+//! - Owners, approved accounts, operators and admins can transfer tokens
+//! - Stackable items are minted as multi-token editions (`MultiToken` service)
+//! - The standard `Vnft` service is exposed next to the `Nft` admin/extension service
 //!
 //! This contract does NOT handle payments - all economic logic
 //! is in the marketplace contract.
 
 use sails_rs::prelude::*;
 mod services;
+pub use services::multi_token::MultiTokenService;
 pub use services::nft::NftService;
+pub use services::vnft::VnftService;
 
 /// NFT Program
 pub struct NftProgram(());
@@ -26,6 +30,7 @@ impl NftProgram {
     ///   The deployer is always added as an admin
     pub fn new(initial_admins: Option<Vec<ActorId>>) -> Self {
         NftService::init(initial_admins);
+        MultiTokenService::init();
         Self(())
     }
 
@@ -33,4 +38,14 @@ impl NftProgram {
     pub fn nft(&self) -> NftService {
         NftService::new()
     }
+
+    /// Get the standard VNFT service
+    pub fn vnft(&self) -> VnftService {
+        VnftService::new()
+    }
+
+    /// Get the multi-token (editions) service
+    pub fn multi_token(&self) -> MultiTokenService {
+        MultiTokenService::new()
+    }
 }
@@ -1,3 +1,5 @@
 //! NFT Contract Services
 
+pub mod multi_token;
 pub mod nft;
+pub mod vnft;
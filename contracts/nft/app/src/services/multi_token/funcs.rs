@@ -0,0 +1,96 @@
+//! Pure functions for multi-token operations
+//!
+//! These functions contain no side effects and are easy to test.
+
+use sails_rs::{collections::HashMap, prelude::*};
+
+/// Get the balance of an account for an edition
+pub fn balance_of(balances: &HashMap<(u64, ActorId), u64>, id: u64, account: ActorId) -> u64 {
+    balances.get(&(id, account)).copied().unwrap_or(0)
+}
+
+/// Credit copies of an edition to an account
+pub fn mint(balances: &mut HashMap<(u64, ActorId), u64>, id: u64, to: ActorId, amount: u64) {
+    let balance = balances.entry((id, to)).or_insert(0);
+    *balance = balance.checked_add(amount).expect("Balance overflow");
+}
+
+/// Debit copies of an edition from an account
+/// 
+/// Zero balances are removed from storage
+pub fn burn(balances: &mut HashMap<(u64, ActorId), u64>, id: u64, from: ActorId, amount: u64) {
+    if amount == 0 {
+        panic!("Amount must be greater than zero");
+    }
+    let balance = balance_of(balances, id, from);
+    if balance < amount {
+        panic!("Insufficient balance");
+    }
+    if balance == amount {
+        balances.remove(&(id, from));
+    } else {
+        balances.insert((id, from), balance - amount);
+    }
+}
+
+/// Move copies of an edition between accounts
+/// 
+/// Panics on a zero amount, so no empty balance entries are created
+pub fn transfer(
+    balances: &mut HashMap<(u64, ActorId), u64>,
+    id: u64,
+    from: ActorId,
+    to: ActorId,
+    amount: u64,
+) {
+    burn(balances, id, from, amount);
+    mint(balances, id, to, amount);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alice() -> ActorId { ActorId::from(1u64) }
+    fn bob() -> ActorId { ActorId::from(2u64) }
+
+    #[test]
+    fn test_transfer_moves_balance() {
+        let mut balances = HashMap::new();
+        mint(&mut balances, 1, alice(), 10);
+
+        transfer(&mut balances, 1, alice(), bob(), 4);
+
+        assert_eq!(balance_of(&balances, 1, alice()), 6);
+        assert_eq!(balance_of(&balances, 1, bob()), 4);
+        // Other editions are unaffected
+        assert_eq!(balance_of(&balances, 2, bob()), 0);
+    }
+
+    #[test]
+    fn test_burn_full_balance_removes_entry() {
+        let mut balances = HashMap::new();
+        mint(&mut balances, 1, alice(), 3);
+
+        burn(&mut balances, 1, alice(), 3);
+
+        assert!(balances.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Amount must be greater than zero")]
+    fn test_transfer_zero_amount_panics() {
+        let mut balances = HashMap::new();
+
+        transfer(&mut balances, 1, alice(), bob(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Insufficient balance")]
+    fn test_transfer_insufficient_balance() {
+        let mut balances = HashMap::new();
+        mint(&mut balances, 1, alice(), 3);
+
+        transfer(&mut balances, 1, alice(), bob(), 4);
+    }
+}
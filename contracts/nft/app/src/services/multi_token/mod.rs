@@ -0,0 +1,409 @@
+//! Multi-Token Service - ERC-1155-style semi-fungible editions
+//!
+//! Stackable game items (potions, skins, ...) are minted as editions:
+//! one ID with many identical copies and an optional max supply.
+//! Balances are kept per (edition, account).
+//! Admins are shared with the `Nft` service. Operators are approved per
+//! service: an operator approved here cannot move the owner's NFTs.
+
+#![allow(static_mut_refs)]
+
+use sails_rs::{
+    collections::{HashMap, HashSet},
+    gstd::{msg, service},
+    prelude::*,
+};
+
+use super::nft;
+
+mod funcs;
+
+/// Maximum number of entries in a batch call
+const MAX_BATCH_SIZE: usize = 100;
+
+/// A semi-fungible edition
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub struct Edition {
+    /// Edition ID (assigned sequentially from 1)
+    pub id: u64,
+    /// Maximum number of copies that can be minted (None = unlimited)
+    pub max_supply: Option<u64>,
+    /// Number of copies minted (burned copies are not subtracted)
+    pub minted: u64,
+    /// Number of copies currently in existence
+    pub supply: u64,
+    /// Metadata URI
+    pub uri: String,
+}
+
+/// Storage for the multi-token service
+#[derive(Default)]
+pub struct Storage {
+    /// Editions: edition_id → edition
+    pub editions: HashMap<u64, Edition>,
+    /// Balances: (edition_id, account) → copies
+    pub balances: HashMap<(u64, ActorId), u64>,
+    /// Last assigned edition ID
+    pub last_edition_id: u64,
+    /// Operator approvals: (owner, operator) pairs (separate from the `Nft` service's)
+    pub operator_approvals: HashSet<(ActorId, ActorId)>,
+}
+
+static mut STORAGE: Option<Storage> = None;
+
+impl Storage {
+    pub fn get_mut() -> &'static mut Self {
+        unsafe { STORAGE.as_mut().expect("Storage is not initialized") }
+    }
+    pub fn get() -> &'static Self {
+        unsafe { STORAGE.as_ref().expect("Storage is not initialized") }
+    }
+}
+
+/// Events emitted by the multi-token service
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub enum Event {
+    /// Copies of one edition moved (from is zero on mint, to is zero on burn)
+    TransferSingle {
+        operator: ActorId,
+        from: ActorId,
+        to: ActorId,
+        id: u64,
+        amount: u64,
+    },
+    /// Copies of several editions moved in one call
+    TransferBatch {
+        operator: ActorId,
+        from: ActorId,
+        to: ActorId,
+        ids: Vec<u64>,
+        amounts: Vec<u64>,
+    },
+    /// Operator approved or revoked for all editions of an owner
+    ApprovalForAll {
+        owner: ActorId,
+        operator: ActorId,
+        approved: bool,
+    },
+    /// Edition created
+    EditionCreated {
+        id: u64,
+        max_supply: Option<u64>,
+        uri: String,
+    },
+}
+
+/// Multi-Token Service
+#[derive(Clone)]
+pub struct MultiTokenService;
+
+impl MultiTokenService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Initialize multi-token storage
+    pub fn init() -> Self {
+        unsafe {
+            STORAGE = Some(Storage::default());
+        }
+        Self
+    }
+}
+
+#[service(events = Event)]
+impl MultiTokenService {
+    // =========================================================================
+    // ADMIN-ONLY FUNCTIONS
+    // =========================================================================
+
+    /// Create a new edition
+    ///
+    /// # Arguments
+    /// * `max_supply` - Maximum number of copies (None = unlimited)
+    /// * `uri` - Metadata URI
+    ///
+    /// # Returns
+    /// The new edition ID
+    ///
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If max_supply is zero
+    #[export]
+    pub fn create_edition(&mut self, max_supply: Option<u64>, uri: String) -> u64 {
+        ensure_admin();
+
+        if max_supply == Some(0) {
+            panic!("Max supply must be greater than zero");
+        }
+
+        let storage = Storage::get_mut();
+        storage.last_edition_id = storage.last_edition_id
+            .checked_add(1)
+            .expect("Edition ID overflow");
+        let id = storage.last_edition_id;
+
+        storage.editions.insert(id, Edition {
+            id,
+            max_supply,
+            minted: 0,
+            supply: 0,
+            uri: uri.clone(),
+        });
+
+        self.emit_event(Event::EditionCreated { id, max_supply, uri })
+            .expect("Notification Error");
+
+        id
+    }
+
+    /// Mint copies of an edition
+    ///
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If the edition does not exist
+    /// - If amount is zero or would exceed the edition's max supply
+    #[export]
+    pub fn mint(&mut self, to: ActorId, id: u64, amount: u64) -> bool {
+        ensure_admin();
+
+        if to.is_zero() {
+            panic!("Cannot mint to zero address");
+        }
+        if amount == 0 {
+            panic!("Amount must be greater than zero");
+        }
+
+        let storage = Storage::get_mut();
+        let edition = storage.editions.get_mut(&id)
+            .unwrap_or_else(|| panic!("Edition does not exist: {}", id));
+        let minted = edition.minted.checked_add(amount).expect("Supply overflow");
+        if edition.max_supply.is_some_and(|max| minted > max) {
+            panic!("Edition max supply exceeded: {}", id);
+        }
+        edition.minted = minted;
+        edition.supply += amount;
+
+        funcs::mint(&mut storage.balances, id, to, amount);
+
+        self.emit_event(Event::TransferSingle {
+            operator: msg::source(),
+            from: ActorId::zero(),
+            to,
+            id,
+            amount,
+        }).expect("Notification Error");
+
+        true
+    }
+
+    // =========================================================================
+    // HOLDER FUNCTIONS
+    // =========================================================================
+
+    /// Transfer copies of an edition
+    ///
+    /// # Panics
+    /// - If caller is not `from`, an operator of `from` or an admin
+    /// - If `to` is the zero address
+    /// - If amount is zero or `from` has insufficient balance
+    #[export]
+    pub fn transfer_from(&mut self, from: ActorId, to: ActorId, id: u64, amount: u64) -> bool {
+        let operator = msg::source();
+        ensure_can_move(operator, from);
+        if to.is_zero() {
+            panic!("Cannot transfer to zero address");
+        }
+
+        funcs::transfer(&mut Storage::get_mut().balances, id, from, to, amount);
+
+        self.emit_event(Event::TransferSingle {
+            operator,
+            from,
+            to,
+            id,
+            amount,
+        }).expect("Notification Error");
+
+        true
+    }
+
+    /// Transfer copies of several editions in one call
+    ///
+    /// Either all transfers succeed or none do.
+    ///
+    /// # Panics
+    /// - If caller is not `from`, an operator of `from` or an admin
+    /// - If `to` is the zero address
+    /// - If ids and amounts differ in length, are empty or exceed `MAX_BATCH_SIZE`
+    /// - If any amount is zero or `from` has insufficient balance for any entry
+    #[export]
+    pub fn batch_transfer_from(
+        &mut self,
+        from: ActorId,
+        to: ActorId,
+        ids: Vec<u64>,
+        amounts: Vec<u64>,
+    ) -> bool {
+        let operator = msg::source();
+        ensure_can_move(operator, from);
+        if to.is_zero() {
+            panic!("Cannot transfer to zero address");
+        }
+        ensure_valid_batch(&ids, &amounts);
+
+        let balances = &mut Storage::get_mut().balances;
+        for (id, amount) in ids.iter().zip(amounts.iter()) {
+            funcs::transfer(balances, *id, from, to, *amount);
+        }
+
+        self.emit_event(Event::TransferBatch {
+            operator,
+            from,
+            to,
+            ids,
+            amounts,
+        }).expect("Notification Error");
+
+        true
+    }
+
+    /// Burn copies of an edition (e.g. consuming an item)
+    ///
+    /// # Panics
+    /// - If caller is not `from`, an operator of `from` or an admin
+    /// - If amount is zero or `from` has insufficient balance
+    #[export]
+    pub fn burn(&mut self, from: ActorId, id: u64, amount: u64) -> bool {
+        let operator = msg::source();
+        ensure_can_move(operator, from);
+
+        let storage = Storage::get_mut();
+        funcs::burn(&mut storage.balances, id, from, amount);
+        if let Some(edition) = storage.editions.get_mut(&id) {
+            edition.supply -= amount;
+        }
+
+        self.emit_event(Event::TransferSingle {
+            operator,
+            from,
+            to: ActorId::zero(),
+            id,
+            amount,
+        }).expect("Notification Error");
+
+        true
+    }
+
+    /// Approve or revoke an operator for all of the caller's editions
+    ///
+    /// Does not cover the caller's NFTs, which are approved through the
+    /// `Nft` service's `set_approval_for_all`.
+    ///
+    /// # Panics
+    /// - If operator is the caller
+    #[export]
+    pub fn set_approval_for_all(&mut self, operator: ActorId, approved: bool) -> bool {
+        let owner = msg::source();
+        if operator == owner {
+            panic!("Cannot set approval for self");
+        }
+
+        let operator_approvals = &mut Storage::get_mut().operator_approvals;
+        if approved {
+            operator_approvals.insert((owner, operator));
+        } else {
+            operator_approvals.remove(&(owner, operator));
+        }
+
+        self.emit_event(Event::ApprovalForAll {
+            owner,
+            operator,
+            approved,
+        }).expect("Notification Error");
+
+        true
+    }
+
+    // =========================================================================
+    // QUERY FUNCTIONS
+    // =========================================================================
+
+    /// Get the number of copies of an edition held by an account
+    #[export]
+    pub fn balance_of(&self, account: ActorId, id: u64) -> u64 {
+        funcs::balance_of(&Storage::get().balances, id, account)
+    }
+
+    /// Get balances for several (account, edition) pairs
+    ///
+    /// # Panics
+    /// - If accounts and ids differ in length or exceed `MAX_BATCH_SIZE`
+    #[export]
+    pub fn balance_of_batch(&self, accounts: Vec<ActorId>, ids: Vec<u64>) -> Vec<u64> {
+        if accounts.len() != ids.len() {
+            panic!("Accounts and ids length mismatch");
+        }
+        if accounts.len() > MAX_BATCH_SIZE {
+            panic!("Batch too large: maximum is {} entries", MAX_BATCH_SIZE);
+        }
+
+        let balances = &Storage::get().balances;
+        accounts.iter()
+            .zip(ids.iter())
+            .map(|(account, id)| funcs::balance_of(balances, *id, *account))
+            .collect()
+    }
+
+    /// Get an edition by ID
+    #[export]
+    pub fn edition(&self, id: u64) -> Option<Edition> {
+        Storage::get().editions.get(&id).cloned()
+    }
+
+    /// Get the metadata URI of an edition
+    #[export]
+    pub fn uri(&self, id: u64) -> Option<String> {
+        Storage::get().editions.get(&id).map(|edition| edition.uri.clone())
+    }
+
+    /// Check if an operator is approved for all editions of an owner
+    #[export]
+    pub fn is_approved_for_all(&self, owner: ActorId, operator: ActorId) -> bool {
+        Storage::get().operator_approvals.contains(&(owner, operator))
+    }
+}
+
+fn ensure_admin() {
+    if !nft::Storage::get().admins.contains(&msg::source()) {
+        panic!("Not admin: only admin can perform this action");
+    }
+}
+
+/// Panic unless `operator` is `from`, an approved operator of `from` or an admin
+fn ensure_can_move(operator: ActorId, from: ActorId) {
+    if operator != from
+        && !Storage::get().operator_approvals.contains(&(from, operator))
+        && !nft::Storage::get().admins.contains(&operator)
+    {
+        panic!("Not authorized: caller is not owner, operator or admin");
+    }
+}
+
+/// Panic unless ids and amounts form a non-empty, bounded batch
+fn ensure_valid_batch(ids: &[u64], amounts: &[u64]) {
+    if ids.len() != amounts.len() {
+        panic!("Ids and amounts length mismatch");
+    }
+    if ids.is_empty() {
+        panic!("Batch cannot be empty");
+    }
+    if ids.len() > MAX_BATCH_SIZE {
+        panic!("Batch too large: maximum is {} entries", MAX_BATCH_SIZE);
+    }
+}
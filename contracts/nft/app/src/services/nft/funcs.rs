@@ -2,11 +2,20 @@
 //!
 //! These functions contain no side effects and are easy to test.
 
-use sails_rs::{collections::HashMap, prelude::*};
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use sails_rs::{
+    collections::{BTreeSet, HashMap, HashSet},
+    prelude::*,
+};
 
 /// Mint a new token
+/// 
+/// An empty metadata URI is not stored, so the token URI falls back to the
+/// base URI
 pub fn mint(
     owners: &mut HashMap<u64, ActorId>,
+    tokens_by_owner: &mut HashMap<ActorId, BTreeSet<u64>>,
     token_uris: &mut HashMap<u64, String>,
     total_supply: &mut u64,
     to: ActorId,
@@ -14,17 +23,348 @@ pub fn mint(
     metadata_uri: String,
 ) {
     owners.insert(token_id, to);
-    token_uris.insert(token_id, metadata_uri);
+    tokens_by_owner.entry(to).or_default().insert(token_id);
+    if !metadata_uri.is_empty() {
+        token_uris.insert(token_id, metadata_uri);
+    }
     *total_supply += 1;
 }
 
 /// Transfer a token to a new owner
+/// 
+/// Any per-token approval is cleared
 pub fn transfer(
     owners: &mut HashMap<u64, ActorId>,
+    tokens_by_owner: &mut HashMap<ActorId, BTreeSet<u64>>,
+    token_approvals: &mut HashMap<u64, ActorId>,
     token_id: u64,
     to: ActorId,
 ) {
-    owners.insert(token_id, to);
+    if let Some(from) = owners.insert(token_id, to) {
+        remove_from_owner_index(tokens_by_owner, from, token_id);
+    }
+    tokens_by_owner.entry(to).or_default().insert(token_id);
+    token_approvals.remove(&token_id);
+}
+
+/// Burn a token, removing its owner, URI and approval
+/// 
+/// Returns the owner the token was burned from
+pub fn burn(
+    owners: &mut HashMap<u64, ActorId>,
+    tokens_by_owner: &mut HashMap<ActorId, BTreeSet<u64>>,
+    token_uris: &mut HashMap<u64, String>,
+    token_approvals: &mut HashMap<u64, ActorId>,
+    burned: &mut HashSet<u64>,
+    token_id: u64,
+) -> Option<ActorId> {
+    let owner = owners.remove(&token_id)?;
+    remove_from_owner_index(tokens_by_owner, owner, token_id);
+    token_uris.remove(&token_id);
+    token_approvals.remove(&token_id);
+    burned.insert(token_id);
+    Some(owner)
+}
+
+/// Remove a token from its owner's index entry, dropping empty entries
+fn remove_from_owner_index(
+    tokens_by_owner: &mut HashMap<ActorId, BTreeSet<u64>>,
+    owner: ActorId,
+    token_id: u64,
+) {
+    if let Some(tokens) = tokens_by_owner.get_mut(&owner) {
+        tokens.remove(&token_id);
+        if tokens.is_empty() {
+            tokens_by_owner.remove(&owner);
+        }
+    }
+}
+
+/// Find the next unused token ID after `last_token_id`
+/// 
+/// Skips IDs that are currently owned or were burned, so auto-assigned IDs
+/// never collide with IDs chosen explicitly through `mint`.
+pub fn next_token_id(
+    owners: &HashMap<u64, ActorId>,
+    burned: &HashSet<u64>,
+    last_token_id: u64,
+) -> u64 {
+    let mut token_id = last_token_id.checked_add(1).expect("Token ID overflow");
+    while owners.contains_key(&token_id) || burned.contains(&token_id) {
+        token_id = token_id.checked_add(1).expect("Token ID overflow");
+    }
+    token_id
+}
+
+/// Resolve a token URI
+/// 
+/// Uses the explicit URI if set, otherwise base + token ID, preferring the
+/// collection base URI over the global one. Empty base URIs are ignored.
+pub fn resolve_token_uri(
+    explicit_uri: Option<&String>,
+    collection_base_uri: Option<&str>,
+    base_uri: &str,
+    token_id: u64,
+) -> Option<String> {
+    if let Some(uri) = explicit_uri {
+        return Some(uri.clone());
+    }
+    collection_base_uri
+        .filter(|base| !base.is_empty())
+        .or(Some(base_uri).filter(|base| !base.is_empty()))
+        .map(|base| format!("{base}{token_id}"))
+}
+
+/// Compute the commitment for a hidden metadata batch
+/// 
+/// blake2b-256 of the SCALE encoding of `(salt, uris)`, i.e. of `RevealData`
+pub fn compute_reveal_hash(salt: &[u8; 32], uris: &[String]) -> [u8; 32] {
+    Blake2b::<U32>::digest((salt, uris).encode()).into()
+}
+
+/// Derive the slot offset of a hidden batch from a random seed
+/// 
+/// The token minted at position `i` receives the URI of slot
+/// `(i + offset) % size`.
+pub fn compute_reveal_offset(seed: &[u8; 32], size: u32) -> u32 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&seed[..8]);
+    (u64::from_le_bytes(bytes) % u64::from(size)) as u32
+}
+
+/// Calculate a royalty amount from a sale price
+/// 
+/// # Arguments
+/// * `sale_price` - The sale price
+/// * `royalty_bps` - Royalty in basis points (100 = 1%)
+/// 
+/// # Returns
+/// The royalty amount (zero on overflow)
+pub fn calculate_royalty(sale_price: U256, royalty_bps: u32) -> U256 {
+    sale_price
+        .checked_mul(U256::from(royalty_bps))
+        .map(|product| product / U256::from(10000u32))
+        .unwrap_or_default()
+}
+
+/// Get a page of items starting at `offset`
+pub fn paginate<T>(items: impl Iterator<Item = T>, offset: u32, limit: u32) -> Vec<T> {
+    items.skip(offset as usize).take(limit as usize).collect()
+}
+
+/// Check if an account may transfer a token: its owner,
+/// the account approved for it, or an operator of the owner
+pub fn is_approved_or_owner(
+    owners: &HashMap<u64, ActorId>,
+    token_approvals: &HashMap<u64, ActorId>,
+    operator_approvals: &HashSet<(ActorId, ActorId)>,
+    spender: ActorId,
+    token_id: u64,
+) -> bool {
+    let Some(owner) = owners.get(&token_id) else {
+        return false;
+    };
+    *owner == spender
+        || token_approvals.get(&token_id) == Some(&spender)
+        || operator_approvals.contains(&(*owner, spender))
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    fn owner() -> ActorId { ActorId::from(1u64) }
+    fn spender() -> ActorId { ActorId::from(2u64) }
+    fn buyer() -> ActorId { ActorId::from(3u64) }
+
+    fn minted_token() -> HashMap<u64, ActorId> {
+        let mut owners = HashMap::new();
+        let mut tokens_by_owner = HashMap::new();
+        let mut token_uris = HashMap::new();
+        let mut total_supply = 0u64;
+        mint(
+            &mut owners,
+            &mut tokens_by_owner,
+            &mut token_uris,
+            &mut total_supply,
+            owner(),
+            1,
+            "ipfs://meta1".into(),
+        );
+        owners
+    }
+
+    #[test]
+    fn test_owner_is_authorized() {
+        let owners = minted_token();
+        assert!(is_approved_or_owner(&owners, &HashMap::new(), &HashSet::new(), owner(), 1));
+    }
+
+    #[test]
+    fn test_approved_account_is_authorized() {
+        let owners = minted_token();
+        let mut token_approvals = HashMap::new();
+        token_approvals.insert(1, spender());
+
+        assert!(is_approved_or_owner(&owners, &token_approvals, &HashSet::new(), spender(), 1));
+        assert!(!is_approved_or_owner(&owners, &token_approvals, &HashSet::new(), buyer(), 1));
+    }
+
+    #[test]
+    fn test_operator_is_authorized() {
+        let owners = minted_token();
+        let mut operator_approvals = HashSet::new();
+        operator_approvals.insert((owner(), spender()));
+
+        assert!(is_approved_or_owner(&owners, &HashMap::new(), &operator_approvals, spender(), 1));
+    }
+
+    #[test]
+    fn test_nonexistent_token_is_not_authorized() {
+        let owners = minted_token();
+        assert!(!is_approved_or_owner(&owners, &HashMap::new(), &HashSet::new(), owner(), 999));
+    }
+
+    #[test]
+    fn test_transfer_clears_approval() {
+        let mut owners = minted_token();
+        let mut token_approvals = HashMap::new();
+        token_approvals.insert(1, spender());
+
+        transfer(&mut owners, &mut HashMap::new(), &mut token_approvals, 1, buyer());
+
+        assert_eq!(owners.get(&1), Some(&buyer()));
+        assert!(token_approvals.get(&1).is_none());
+    }
+
+    #[test]
+    fn test_owner_index_follows_mint_and_transfer() {
+        let mut owners = HashMap::new();
+        let mut tokens_by_owner = HashMap::new();
+        let mut token_uris = HashMap::new();
+        let mut total_supply = 0u64;
+        for token_id in 1..=3 {
+            mint(
+                &mut owners,
+                &mut tokens_by_owner,
+                &mut token_uris,
+                &mut total_supply,
+                owner(),
+                token_id,
+                format!("ipfs://meta{token_id}"),
+            );
+        }
+
+        transfer(&mut owners, &mut tokens_by_owner, &mut HashMap::new(), 2, buyer());
+
+        let owner_tokens: Vec<u64> = tokens_by_owner[&owner()].iter().copied().collect();
+        assert_eq!(owner_tokens, vec![1, 3]);
+        let buyer_tokens: Vec<u64> = tokens_by_owner[&buyer()].iter().copied().collect();
+        assert_eq!(buyer_tokens, vec![2]);
+
+        // Moving the last token out drops the owner's entry
+        transfer(&mut owners, &mut tokens_by_owner, &mut HashMap::new(), 2, owner());
+        assert!(!tokens_by_owner.contains_key(&buyer()));
+    }
+
+    #[test]
+    fn test_burn_removes_token() {
+        let mut owners = HashMap::new();
+        let mut tokens_by_owner = HashMap::new();
+        let mut token_uris = HashMap::new();
+        let mut token_approvals = HashMap::new();
+        let mut burned = HashSet::new();
+        let mut total_supply = 0u64;
+        mint(
+            &mut owners,
+            &mut tokens_by_owner,
+            &mut token_uris,
+            &mut total_supply,
+            owner(),
+            1,
+            "ipfs://meta1".into(),
+        );
+        token_approvals.insert(1, spender());
+
+        let from = burn(
+            &mut owners,
+            &mut tokens_by_owner,
+            &mut token_uris,
+            &mut token_approvals,
+            &mut burned,
+            1,
+        );
+
+        assert_eq!(from, Some(owner()));
+        assert!(owners.is_empty());
+        assert!(tokens_by_owner.is_empty());
+        assert!(token_uris.is_empty());
+        assert!(token_approvals.is_empty());
+        assert!(burned.contains(&1));
+        // Minted count is unaffected by burning
+        assert_eq!(total_supply, 1);
+    }
+
+    #[test]
+    fn test_next_token_id_skips_used_ids() {
+        let owners = minted_token();
+        let burned = HashSet::from([2u64]);
+
+        assert_eq!(next_token_id(&owners, &burned, 0), 3);
+        assert_eq!(next_token_id(&owners, &burned, 3), 4);
+    }
+
+    #[test]
+    fn test_resolve_token_uri() {
+        let explicit = String::from("ipfs://explicit");
+        assert_eq!(
+            resolve_token_uri(Some(&explicit), Some("ipfs://season1/"), "ipfs://base/", 7),
+            Some(explicit.clone())
+        );
+        assert_eq!(
+            resolve_token_uri(None, Some("ipfs://season1/"), "ipfs://base/", 7),
+            Some("ipfs://season1/7".into())
+        );
+        assert_eq!(
+            resolve_token_uri(None, Some(""), "ipfs://base/", 7),
+            Some("ipfs://base/7".into())
+        );
+        assert_eq!(resolve_token_uri(None, None, "", 7), None);
+    }
+
+    #[test]
+    fn test_reveal_hash_binds_salt_and_order() {
+        let uris = vec![String::from("ipfs://a"), String::from("ipfs://b")];
+        let swapped = vec![String::from("ipfs://b"), String::from("ipfs://a")];
+        let hash = compute_reveal_hash(&[1u8; 32], &uris);
+
+        assert_eq!(hash, compute_reveal_hash(&[1u8; 32], &uris));
+        assert_ne!(hash, compute_reveal_hash(&[2u8; 32], &uris));
+        assert_ne!(hash, compute_reveal_hash(&[1u8; 32], &swapped));
+    }
+
+    #[test]
+    fn test_reveal_offset_is_within_batch() {
+        let mut seed = [0u8; 32];
+        seed[0] = 7;
+        assert_eq!(compute_reveal_offset(&seed, 5), 2);
+        assert_eq!(compute_reveal_offset(&seed, 1), 0);
+        assert!(compute_reveal_offset(&[0xff; 32], 500) < 500);
+    }
+
+    #[test]
+    fn test_calculate_royalty() {
+        // 1000 at 500 bps (5%) = 50
+        assert_eq!(calculate_royalty(U256::from(1000), 500), U256::from(50));
+        // Rounds down
+        assert_eq!(calculate_royalty(U256::from(99), 100), U256::zero());
+        assert_eq!(calculate_royalty(U256::from(1000), 0), U256::zero());
+    }
+
+    #[test]
+    fn test_paginate() {
+        assert_eq!(paginate(1..=5u64, 0, 2), vec![1, 2]);
+        assert_eq!(paginate(1..=5u64, 4, 2), vec![5]);
+        assert!(paginate(1..=5u64, 10, 2).is_empty());
+    }
+}
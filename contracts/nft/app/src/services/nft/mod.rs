@@ -1,30 +1,283 @@
 //! NFT Service - Core implementation
 //!
-//! Implements a minimal, marketplace-controlled NFT contract.
-//! Only admins can mint and transfer tokens.
-//! No approvals, no public transfers, no payment logic.
+//! Implements a marketplace-friendly NFT contract.
+//! Only admins can mint tokens.
+//! Players can also redeem backend-signed mint vouchers (lazy minting).
+//! Admins can also create collections (e.g. seasonal sets) with their own
+//! max supply and minters, who can mint into that collection only.
+//! Owners can transfer their tokens and delegate transfers via
+//! per-token approvals and operators; admins can transfer any token.
+//! Safe transfers notify receiver programs and roll back if they reject.
+//! Registered locker programs (marketplace, staking) can lock approved
+//! tokens in place; locked tokens can only be moved by their locker.
+//! Tokens can be nested under a parent token (e.g. equipment on a character)
+//! and then move with the parent; children from whitelisted NFT programs are
+//! held in escrow by this program while nested.
+//! Mystery drops mint with placeholder metadata committed to a hidden batch;
+//! closing the batch fixes a random slot offset, and the real URIs are
+//! revealed later and checked against the commitment.
+//! Owners can rent out usage rights with a time-bounded user role (ERC-4907).
+//! Tokens can be burned by their owner, an approved account or an admin.
+//! Soulbound tokens (e.g. achievement badges) cannot be transferred at all,
+//! only burned.
+//! No payment logic.
 
 #![allow(static_mut_refs)]
 
+use nft_client::NftClient;
+use receiver::ReceiverClient;
 use sails_rs::{
-    collections::{HashMap, HashSet},
-    gstd::{msg, service},
+    collections::{BTreeSet, HashMap, HashSet},
+    gstd::{exec, msg, service},
     prelude::*,
 };
 
+use super::vnft;
+
 mod funcs;
+mod nft_client;
+mod receiver;
+mod voucher;
+pub use voucher::{MintVoucher, VoucherTarget};
+
+/// Maximum number of items returned by paginated queries
+const MAX_PAGE_SIZE: u32 = 100;
+
+/// Maximum number of key/value attributes per token
+const MAX_ATTRIBUTES: usize = 32;
+
+/// Maximum royalty in basis points (10%)
+const MAX_ROYALTY_BPS: u32 = 1000;
+
+/// Maximum number of tokens minted by a single `batch_mint` call
+const MAX_BATCH_MINT: usize = 100;
+
+/// Maximum number of children nested under one parent
+const MAX_CHILDREN: usize = 16;
+
+/// Maximum number of tokens in a hidden metadata batch
+const MAX_HIDDEN_BATCH_SIZE: u32 = 500;
+
+/// Blocks to wait for another program's reply before treating the call as
+/// failed (one minute at 3-second blocks)
+const REPLY_TIMEOUT_BLOCKS: u32 = 20;
+
+/// Token rarity (mirrors the off-chain `NFTRarity` enum)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub enum Rarity {
+    Common,
+    Rare,
+    Epic,
+    Legendary,
+    Mythic,
+}
+
+/// Structured on-chain token metadata
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub struct TokenMetadata {
+    /// Display name
+    pub name: String,
+    /// Optional description
+    pub description: Option<String>,
+    /// Image URI
+    pub image: String,
+    /// Rarity tier
+    pub rarity: Rarity,
+    /// Optional collection name
+    pub collection: Option<String>,
+    /// Key/value attributes (at most `MAX_ATTRIBUTES`)
+    pub attributes: Vec<(String, String)>,
+}
+
+/// A collection of tokens within this program
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub struct Collection {
+    /// Collection ID (assigned sequentially from 1)
+    pub id: u32,
+    /// Display name
+    pub name: String,
+    /// Short symbol
+    pub symbol: String,
+    /// Maximum number of tokens that can be minted (None = unlimited)
+    pub max_supply: Option<u64>,
+    /// Number of tokens minted into the collection (including burned)
+    pub minted: u64,
+    /// Base URI for tokens in the collection
+    pub base_uri: String,
+    /// Whether tokens in the collection are non-transferable
+    pub soulbound: bool,
+}
+
+/// Royalty paid to a recipient on secondary sales
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub struct Royalty {
+    /// Account receiving the royalty
+    pub recipient: ActorId,
+    /// Royalty in basis points (100 = 1%)
+    pub bps: u32,
+}
+
+/// What a metadata update or freeze applies to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub enum MetadataScope {
+    /// A single token
+    Token(u64),
+    /// All tokens of a collection
+    Collection(u32),
+    /// All tokens
+    All,
+}
+
+/// Time-bounded user of a token (ERC-4907)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub struct TokenUser {
+    /// Account allowed to use the token
+    pub user: ActorId,
+    /// Expiry timestamp in milliseconds
+    pub expires_ms: u64,
+}
+
+/// Lock held on a token by a locker program
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub struct TokenLock {
+    /// Program holding the lock
+    pub locker: ActorId,
+    /// Locker-defined lock ID (e.g. listing or stake ID)
+    pub lock_id: u64,
+}
+
+/// Token nested under a parent, in this or a whitelisted NFT program
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub struct ChildToken {
+    /// NFT program the child belongs to
+    pub program: ActorId,
+    /// Child token ID in that program
+    pub token_id: u64,
+}
+
+/// Batch of tokens minted with placeholder metadata until revealed
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub struct HiddenBatch {
+    /// Batch ID (assigned sequentially from 1)
+    pub id: u64,
+    /// URI shown for every token until reveal
+    pub placeholder_uri: String,
+    /// blake2b-256 of the SCALE-encoded `RevealData`
+    pub commitment: [u8; 32],
+    /// Number of slots (the committed URI list has exactly this length)
+    pub size: u32,
+    /// Minted token IDs, in mint order
+    pub token_ids: Vec<u64>,
+    /// Random slot offset, fixed when the batch is closed to further mints
+    pub reveal_offset: Option<u32>,
+    /// Whether the real metadata has been revealed
+    pub revealed: bool,
+}
+
+/// Real metadata of a hidden batch, published at reveal
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub struct RevealData {
+    /// Random salt so the commitment cannot be brute-forced
+    pub salt: [u8; 32],
+    /// Metadata URI for each slot, in slot order
+    pub uris: Vec<String>,
+}
 
 /// Storage for NFT contract
 #[derive(Default)]
 pub struct Storage {
+    /// Program name (shown by wallets and explorers)
+    pub name: String,
+    /// Program symbol
+    pub symbol: String,
     /// NFT ownership: token_id → owner
     pub owners: HashMap<u64, ActorId>,
-    /// NFT metadata: token_id → metadata URI
+    /// Owner index: owner → owned token_ids (kept in sync with `owners`)
+    pub tokens_by_owner: HashMap<ActorId, BTreeSet<u64>>,
+    /// Live token IDs in ascending order (kept in sync with `owners`)
+    pub token_ids: BTreeSet<u64>,
+    /// NFT metadata: token_id → explicit metadata URI
     pub token_uris: HashMap<u64, String>,
-    /// Total number of NFTs minted
+    /// Global base URI (token URI = base + token_id when no explicit URI)
+    pub base_uri: String,
+    /// Tokens whose metadata can no longer change
+    pub frozen_tokens: HashSet<u64>,
+    /// Whether metadata of all tokens is frozen
+    pub metadata_frozen: bool,
+    /// Structured metadata: token_id → metadata (only for tokens minted with it)
+    pub token_metadata: HashMap<u64, TokenMetadata>,
+    /// Total number of NFTs minted (burned tokens are not subtracted)
     pub total_supply: u64,
+    /// Burned token IDs (never minted again)
+    pub burned: HashSet<u64>,
     /// Admins (marketplace and deployer)
     pub admins: HashSet<ActorId>,
+    /// Per-token approvals: token_id → approved account
+    pub token_approvals: HashMap<u64, ActorId>,
+    /// Operator approvals: (owner, operator) pairs
+    pub operator_approvals: HashSet<(ActorId, ActorId)>,
+    /// Collections: collection_id → collection
+    pub collections: HashMap<u32, Collection>,
+    /// Collection minters: (collection_id, minter) pairs
+    pub collection_minters: HashSet<(u32, ActorId)>,
+    /// Token collections: token_id → collection_id (only for collection tokens)
+    pub token_collections: HashMap<u64, u32>,
+    /// Last assigned collection ID
+    pub last_collection_id: u32,
+    /// Per-token royalties (take precedence over collection royalties)
+    pub token_royalties: HashMap<u64, Royalty>,
+    /// Per-collection royalties
+    pub collection_royalties: HashMap<u32, Royalty>,
+    /// Last token ID assigned by `mint_next` / `batch_mint`
+    pub last_auto_token_id: u64,
+
+    /// Token users: token_id → user and expiry (cleared on transfer)
+    pub token_users: HashMap<u64, TokenUser>,
+    /// Programs allowed to lock tokens
+    pub lockers: HashSet<ActorId>,
+    /// Token locks: token_id → lock (cleared when the locker moves the token)
+    pub token_locks: HashMap<u64, TokenLock>,
+    /// Tokens with a cross-program call in flight (cannot move until it ends):
+    /// token_id → last block of the reply wait
+    pub pending_tokens: HashMap<u64, u32>,
+    /// Nested children: parent token_id → children
+    pub children: HashMap<u64, Vec<ChildToken>>,
+    /// Nested children: child → parent token_id
+    pub parents: HashMap<ChildToken, u64>,
+    /// Other NFT programs whose tokens can be nested
+    pub nestable_programs: HashSet<ActorId>,
+    /// Hidden metadata batches: batch_id → batch
+    pub hidden_batches: HashMap<u64, HiddenBatch>,
+    /// Last assigned hidden batch ID
+    pub last_batch_id: u64,
+    /// Soulbound (non-transferable) tokens, in addition to soulbound collections
+    pub soulbound_tokens: HashSet<u64>,
+
+    // === Mint voucher fields ===
+    /// Backend signer sr25519 public key for mint vouchers
+    pub backend_signer: Option<[u8; 32]>,
+    /// Redeemed voucher IDs to prevent replay attacks
+    pub used_vouchers: HashSet<[u8; 32]>,
 }
 
 static mut STORAGE: Option<Storage> = None;
@@ -58,6 +311,150 @@ pub enum Event {
     AdminRemoved {
         admin: ActorId,
     },
+    /// Program name and symbol updated
+    NameAndSymbolUpdated {
+        name: String,
+        symbol: String,
+    },
+    /// Account approved to transfer a single token (zero clears the approval)
+    Approval {
+        owner: ActorId,
+        approved: ActorId,
+        token_id: u64,
+    },
+    /// Operator approved or revoked for all tokens of an owner
+    ApprovalForAll {
+        owner: ActorId,
+        operator: ActorId,
+        approved: bool,
+    },
+    /// Collection created
+    CollectionCreated {
+        collection_id: u32,
+        name: String,
+        symbol: String,
+        max_supply: Option<u64>,
+    },
+    /// Collection minter added
+    CollectionMinterAdded {
+        collection_id: u32,
+        minter: ActorId,
+    },
+    /// Collection minter removed
+    CollectionMinterRemoved {
+        collection_id: u32,
+        minter: ActorId,
+    },
+    /// Token royalty set
+    TokenRoyaltySet {
+        token_id: u64,
+        recipient: ActorId,
+        bps: u32,
+    },
+    /// Collection royalty set
+    CollectionRoyaltySet {
+        collection_id: u32,
+        recipient: ActorId,
+        bps: u32,
+    },
+    /// Token soulbound flag updated
+    SoulboundUpdated {
+        token_id: u64,
+        soulbound: bool,
+    },
+    /// Collection soulbound flag updated
+    CollectionSoulboundUpdated {
+        collection_id: u32,
+        soulbound: bool,
+    },
+    /// Token URI or base URI changed
+    MetadataUpdated {
+        scope: MetadataScope,
+    },
+    /// Metadata permanently frozen
+    MetadataFrozen {
+        scope: MetadataScope,
+    },
+    /// Token user set (zero user clears it)
+    UpdateUser {
+        token_id: u64,
+        user: ActorId,
+        expires_ms: u64,
+    },
+    /// Locker program registered
+    LockerAdded {
+        locker: ActorId,
+    },
+    /// Locker program removed
+    LockerRemoved {
+        locker: ActorId,
+    },
+    /// Token locked in place
+    Locked {
+        token_id: u64,
+        locker: ActorId,
+        lock_id: u64,
+    },
+    /// Token unlocked
+    Unlocked {
+        token_id: u64,
+        locker: ActorId,
+        lock_id: u64,
+    },
+    /// NFT program whitelisted for nesting
+    NestableProgramAdded {
+        program: ActorId,
+    },
+    /// NFT program removed from the nesting whitelist
+    NestableProgramRemoved {
+        program: ActorId,
+    },
+    /// Child nested under a parent token
+    Nested {
+        parent_id: u64,
+        child: ChildToken,
+    },
+    /// Child removed from a parent token
+    Unnested {
+        parent_id: u64,
+        child: ChildToken,
+    },
+    /// Hidden metadata batch created
+    HiddenBatchCreated {
+        batch_id: u64,
+        commitment: [u8; 32],
+        size: u32,
+    },
+    /// Hidden metadata batch closed to further mints
+    HiddenBatchClosed {
+        batch_id: u64,
+        reveal_offset: u32,
+    },
+    /// Hidden metadata batch revealed
+    BatchRevealed {
+        batch_id: u64,
+        token_count: u32,
+    },
+    /// Safe transfer rejected by the receiver and rolled back
+    SafeTransferRejected {
+        from: ActorId,
+        to: ActorId,
+        token_id: u64,
+    },
+    /// Pending flag of a token cleared by an admin after its reply wait
+    PendingCleared {
+        token_id: u64,
+    },
+    /// Backend signer for mint vouchers updated
+    BackendSignerUpdated {
+        pubkey: [u8; 32],
+    },
+    /// Mint voucher redeemed
+    VoucherRedeemed {
+        voucher_id: [u8; 32],
+        recipient: ActorId,
+        token_id: u64,
+    },
 }
 
 /// NFT Service
@@ -104,29 +501,17 @@ impl NftService {
     /// # Arguments
     /// * `to` - The address to mint to (typically marketplace)
     /// * `token_id` - Unique token ID
-    /// * `metadata_uri` - URI pointing to token metadata
+    /// * `metadata_uri` - URI pointing to token metadata (empty = use base URI)
     /// 
     /// # Panics
     /// - If caller is not an admin
+    /// - If `to` is the zero address
     /// - If token_id already exists
     #[export]
     pub fn mint(&mut self, to: ActorId, token_id: u64, metadata_uri: String) -> bool {
         self.ensure_admin();
         
-        let storage = Storage::get();
-        if storage.owners.contains_key(&token_id) {
-            panic!("Token ID already exists: {}", token_id);
-        }
-        
-        let storage = Storage::get_mut();
-        funcs::mint(
-            &mut storage.owners,
-            &mut storage.token_uris,
-            &mut storage.total_supply,
-            to,
-            token_id,
-            metadata_uri,
-        );
+        mint_token(to, token_id, metadata_uri);
         
         // Emit Transfer event with from = zero address (indicating mint)
         self.emit_event(Event::Transfer {
@@ -138,112 +523,1934 @@ impl NftService {
         true
     }
 
-    /// Transfer an NFT from one address to another
+    /// Mint a new NFT with an automatically assigned token ID
+    /// 
+    /// IDs are assigned in increasing order, skipping any ID already taken
+    /// by `mint` or burned.
+    /// 
+    /// # Returns
+    /// The assigned token ID
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If `to` is the zero address
+    #[export]
+    pub fn mint_next(&mut self, to: ActorId, metadata_uri: String) -> u64 {
+        self.ensure_admin();
+
+        let token_id = mint_next_token(to, metadata_uri);
+
+        self.emit_event(Event::Transfer {
+            from: ActorId::zero(),
+            to,
+            token_id,
+        }).expect("Notification Error");
+
+        token_id
+    }
+
+    /// Mint several NFTs with automatically assigned token IDs
+    /// 
+    /// Emits one `Transfer` event per token. Either all tokens are minted or
+    /// none are.
     /// 
     /// # Arguments
-    /// * `from` - Current owner
-    /// * `to` - New owner
-    /// * `token_id` - Token to transfer
+    /// * `items` - (recipient, metadata URI) pairs, at most `MAX_BATCH_MINT`
+    /// 
+    /// # Returns
+    /// The assigned token IDs, in the same order as `items`
     /// 
     /// # Panics
     /// - If caller is not an admin
-    /// - If token_id does not exist
-    /// - If from is not the current owner
+    /// - If items is empty or longer than `MAX_BATCH_MINT`
+    /// - If any recipient is the zero address
     #[export]
-    pub fn transfer_from(&mut self, from: ActorId, to: ActorId, token_id: u64) -> bool {
+    pub fn batch_mint(&mut self, items: Vec<(ActorId, String)>) -> Vec<u64> {
         self.ensure_admin();
-        
-        let storage = Storage::get();
-        let current_owner = storage.owners.get(&token_id)
-            .unwrap_or_else(|| panic!("Token does not exist: {}", token_id));
-        
-        if *current_owner != from {
-            panic!("Not owner: {} does not own token {}", from, token_id);
+
+        if items.is_empty() {
+            panic!("Batch cannot be empty");
         }
-        
-        let storage = Storage::get_mut();
-        funcs::transfer(&mut storage.owners, token_id, to);
-        
+        if items.len() > MAX_BATCH_MINT {
+            panic!("Batch too large: maximum is {} tokens", MAX_BATCH_MINT);
+        }
+
+        let mut token_ids = Vec::with_capacity(items.len());
+        for (to, metadata_uri) in items {
+            let token_id = mint_next_token(to, metadata_uri);
+            self.emit_event(Event::Transfer {
+                from: ActorId::zero(),
+                to,
+                token_id,
+            }).expect("Notification Error");
+            token_ids.push(token_id);
+        }
+
+        token_ids
+    }
+
+    /// Mint a new NFT with structured on-chain metadata
+    /// 
+    /// # Arguments
+    /// * `to` - The address to mint to (typically marketplace)
+    /// * `token_id` - Unique token ID
+    /// * `metadata_uri` - URI pointing to token metadata (empty = use base URI)
+    /// * `metadata` - Name, image, rarity and attributes stored on-chain
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If `to` is the zero address
+    /// - If token_id already exists
+    /// - If metadata has more than `MAX_ATTRIBUTES` attributes
+    #[export]
+    pub fn mint_with_metadata(
+        &mut self,
+        to: ActorId,
+        token_id: u64,
+        metadata_uri: String,
+        metadata: TokenMetadata,
+    ) -> bool {
+        self.ensure_admin();
+
+        if metadata.attributes.len() > MAX_ATTRIBUTES {
+            panic!("Too many attributes: maximum is {}", MAX_ATTRIBUTES);
+        }
+
+        mint_token(to, token_id, metadata_uri);
+        Storage::get_mut().token_metadata.insert(token_id, metadata);
+
         self.emit_event(Event::Transfer {
-            from,
+            from: ActorId::zero(),
             to,
             token_id,
         }).expect("Notification Error");
-        
+
         true
     }
 
-    /// Add a new admin
+    /// Create a new collection
+    /// 
+    /// # Arguments
+    /// * `name` - Display name
+    /// * `symbol` - Short symbol
+    /// * `max_supply` - Maximum number of tokens (None = unlimited)
+    /// * `base_uri` - Base URI for tokens in the collection
+    /// 
+    /// # Returns
+    /// The new collection ID
     /// 
     /// # Panics
     /// - If caller is not an admin
+    /// - If max_supply is zero
     #[export]
-    pub fn add_admin(&mut self, admin: ActorId) -> bool {
+    pub fn create_collection(
+        &mut self,
+        name: String,
+        symbol: String,
+        max_supply: Option<u64>,
+        base_uri: String,
+    ) -> u32 {
         self.ensure_admin();
-        
-        Storage::get_mut().admins.insert(admin);
-        
-        self.emit_event(Event::AdminAdded { admin })
-            .expect("Notification Error");
-        
-        true
+
+        if max_supply == Some(0) {
+            panic!("Max supply must be greater than zero");
+        }
+
+        let storage = Storage::get_mut();
+        storage.last_collection_id = storage.last_collection_id
+            .checked_add(1)
+            .expect("Collection ID overflow");
+        let collection_id = storage.last_collection_id;
+
+        storage.collections.insert(collection_id, Collection {
+            id: collection_id,
+            name: name.clone(),
+            symbol: symbol.clone(),
+            max_supply,
+            minted: 0,
+            base_uri,
+            soulbound: false,
+        });
+
+        self.emit_event(Event::CollectionCreated {
+            collection_id,
+            name,
+            symbol,
+            max_supply,
+        }).expect("Notification Error");
+
+        collection_id
     }
 
-    /// Remove an admin
+    /// Allow an account to mint into a collection
     /// 
     /// # Panics
     /// - If caller is not an admin
+    /// - If the collection does not exist
     #[export]
-    pub fn remove_admin(&mut self, admin: ActorId) -> bool {
+    pub fn add_collection_minter(&mut self, collection_id: u32, minter: ActorId) -> bool {
         self.ensure_admin();
-        
-        Storage::get_mut().admins.remove(&admin);
-        
-        self.emit_event(Event::AdminRemoved { admin })
+        ensure_collection_exists(collection_id);
+
+        Storage::get_mut().collection_minters.insert((collection_id, minter));
+
+        self.emit_event(Event::CollectionMinterAdded { collection_id, minter })
             .expect("Notification Error");
-        
+
         true
     }
 
-    // =========================================================================
-    // QUERY FUNCTIONS
-    // =========================================================================
-
-    /// Get the owner of a token
+    /// Revoke an account's right to mint into a collection
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If the collection does not exist
     #[export]
-    pub fn owner_of(&self, token_id: u64) -> Option<ActorId> {
-        Storage::get().owners.get(&token_id).cloned()
-    }
+    pub fn remove_collection_minter(&mut self, collection_id: u32, minter: ActorId) -> bool {
+        self.ensure_admin();
+        ensure_collection_exists(collection_id);
 
-    /// Get the metadata URI of a token
-    #[export]
-    pub fn token_uri(&self, token_id: u64) -> Option<String> {
-        Storage::get().token_uris.get(&token_id).cloned()
+        Storage::get_mut().collection_minters.remove(&(collection_id, minter));
+
+        self.emit_event(Event::CollectionMinterRemoved { collection_id, minter })
+            .expect("Notification Error");
+
+        true
     }
 
-    /// Get total number of NFTs minted
+    /// Mint a new NFT into a collection
+    /// 
+    /// # Arguments
+    /// * `collection_id` - Collection to mint into
+    /// * `to` - The address to mint to
+    /// * `token_id` - Unique token ID (shared ID space across collections)
+    /// * `metadata_uri` - URI pointing to token metadata (empty = use base URI)
+    /// 
+    /// # Panics
+    /// - If caller is not an admin or a minter of the collection
+    /// - If the collection does not exist or its max supply is reached
+    /// - If `to` is the zero address
+    /// - If token_id already exists
     #[export]
-    pub fn total_supply(&self) -> u64 {
-        Storage::get().total_supply
+    pub fn mint_in_collection(
+        &mut self,
+        collection_id: u32,
+        to: ActorId,
+        token_id: u64,
+        metadata_uri: String,
+    ) -> bool {
+        self.ensure_collection_minter(collection_id);
+
+        mint_token(to, token_id, metadata_uri);
+        add_to_collection(collection_id, token_id);
+
+        self.emit_event(Event::Transfer {
+            from: ActorId::zero(),
+            to,
+            token_id,
+        }).expect("Notification Error");
+
+        true
     }
 
-    /// Get all admins
+    /// Set the royalty for a single token
+    /// 
+    /// Token royalties take precedence over the token's collection royalty.
+    /// Write-once: it can be set at any time while the token exists, but not
+    /// changed or cleared afterwards.
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If token_id does not exist
+    /// - If the token royalty is already set
+    /// - If bps is zero or exceeds `MAX_ROYALTY_BPS`
     #[export]
-    pub fn admins(&self) -> Vec<ActorId> {
-        Storage::get().admins.iter().cloned().collect()
+    pub fn set_token_royalty(&mut self, token_id: u64, recipient: ActorId, bps: u32) -> bool {
+        self.ensure_admin();
+        if !Storage::get().owners.contains_key(&token_id) {
+            panic!("Token does not exist: {}", token_id);
+        }
+
+        set_royalty(&mut Storage::get_mut().token_royalties, token_id, recipient, bps);
+
+        self.emit_event(Event::TokenRoyaltySet { token_id, recipient, bps })
+            .expect("Notification Error");
+
+        true
     }
 
-    /// Check if an account is an admin
+    /// Set the default royalty for all tokens in a collection
+    /// 
+    /// Set once, before minting into the collection; it cannot be changed afterwards.
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If the collection does not exist or tokens were already minted into it
+    /// - If the collection royalty is already set
+    /// - If bps is zero or exceeds `MAX_ROYALTY_BPS`
     #[export]
-    pub fn is_admin(&self, account: ActorId) -> bool {
-        Storage::get().admins.contains(&account)
-    }
-}
+    pub fn set_collection_royalty(
+        &mut self,
+        collection_id: u32,
+        recipient: ActorId,
+        bps: u32,
+    ) -> bool {
+        self.ensure_admin();
+        ensure_collection_exists(collection_id);
+        if Storage::get().collections[&collection_id].minted > 0 {
+            panic!("Collection already has minted tokens: {}", collection_id);
+        }
 
-impl NftService {
+        set_royalty(&mut Storage::get_mut().collection_royalties, collection_id, recipient, bps);
+
+        self.emit_event(Event::CollectionRoyaltySet { collection_id, recipient, bps })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Mark a token as soulbound (non-transferable) or clear the flag
+    /// 
+    /// Soulbound tokens can still be burned by their owner or an admin.
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If token_id does not exist
+    #[export]
+    pub fn set_soulbound(&mut self, token_id: u64, soulbound: bool) -> bool {
+        self.ensure_admin();
+        let storage = Storage::get_mut();
+        if !storage.owners.contains_key(&token_id) {
+            panic!("Token does not exist: {}", token_id);
+        }
+
+        if soulbound {
+            storage.soulbound_tokens.insert(token_id);
+        } else {
+            storage.soulbound_tokens.remove(&token_id);
+        }
+
+        self.emit_event(Event::SoulboundUpdated { token_id, soulbound })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Mark all tokens of a collection as soulbound or clear the flag
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If the collection does not exist
+    #[export]
+    pub fn set_collection_soulbound(&mut self, collection_id: u32, soulbound: bool) -> bool {
+        self.ensure_admin();
+
+        Storage::get_mut().collections.get_mut(&collection_id)
+            .unwrap_or_else(|| panic!("Collection does not exist: {}", collection_id))
+            .soulbound = soulbound;
+
+        self.emit_event(Event::CollectionSoulboundUpdated { collection_id, soulbound })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Register a program allowed to lock tokens
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    #[export]
+    pub fn add_locker(&mut self, locker: ActorId) -> bool {
+        self.ensure_admin();
+
+        Storage::get_mut().lockers.insert(locker);
+
+        self.emit_event(Event::LockerAdded { locker })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Remove a locker program
+    /// 
+    /// Existing locks stay in place until unlocked by the locker or an admin.
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    #[export]
+    pub fn remove_locker(&mut self, locker: ActorId) -> bool {
+        self.ensure_admin();
+
+        Storage::get_mut().lockers.remove(&locker);
+
+        self.emit_event(Event::LockerRemoved { locker })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Allow tokens of another NFT program to be nested
+    /// 
+    /// The program must expose the `Nft` service of this contract.
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    #[export]
+    pub fn add_nestable_program(&mut self, program: ActorId) -> bool {
+        self.ensure_admin();
+
+        Storage::get_mut().nestable_programs.insert(program);
+
+        self.emit_event(Event::NestableProgramAdded { program })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Stop allowing tokens of another NFT program to be nested
+    /// 
+    /// Already nested children can still be unnested.
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    #[export]
+    pub fn remove_nestable_program(&mut self, program: ActorId) -> bool {
+        self.ensure_admin();
+
+        Storage::get_mut().nestable_programs.remove(&program);
+
+        self.emit_event(Event::NestableProgramRemoved { program })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Set the program name and symbol reported by the `Vnft` service
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    #[export]
+    pub fn set_name_and_symbol(&mut self, name: String, symbol: String) -> bool {
+        self.ensure_admin();
+
+        let storage = Storage::get_mut();
+        storage.name = name.clone();
+        storage.symbol = symbol.clone();
+
+        self.emit_event(Event::NameAndSymbolUpdated { name, symbol })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Set the explicit metadata URI of a token
+    /// 
+    /// Pass an empty URI to fall back to the base URI.
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If token_id does not exist
+    /// - If the token's metadata is frozen
+    #[export]
+    pub fn set_token_uri(&mut self, token_id: u64, metadata_uri: String) -> bool {
+        self.ensure_admin();
+        let storage = Storage::get_mut();
+        if !storage.owners.contains_key(&token_id) {
+            panic!("Token does not exist: {}", token_id);
+        }
+        if storage.metadata_frozen || storage.frozen_tokens.contains(&token_id) {
+            panic!("Metadata is frozen: {}", token_id);
+        }
+
+        if metadata_uri.is_empty() {
+            storage.token_uris.remove(&token_id);
+        } else {
+            storage.token_uris.insert(token_id, metadata_uri);
+        }
+
+        self.emit_event(Event::MetadataUpdated { scope: MetadataScope::Token(token_id) })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Set the global base URI
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If all metadata is frozen
+    #[export]
+    pub fn set_base_uri(&mut self, base_uri: String) -> bool {
+        self.ensure_admin();
+        let storage = Storage::get_mut();
+        if storage.metadata_frozen {
+            panic!("Metadata is frozen");
+        }
+
+        storage.base_uri = base_uri;
+
+        self.emit_event(Event::MetadataUpdated { scope: MetadataScope::All })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Set the base URI of a collection
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If the collection does not exist
+    /// - If all metadata is frozen
+    #[export]
+    pub fn set_collection_base_uri(&mut self, collection_id: u32, base_uri: String) -> bool {
+        self.ensure_admin();
+        let storage = Storage::get_mut();
+        if storage.metadata_frozen {
+            panic!("Metadata is frozen");
+        }
+
+        storage.collections.get_mut(&collection_id)
+            .unwrap_or_else(|| panic!("Collection does not exist: {}", collection_id))
+            .base_uri = base_uri;
+
+        self.emit_event(Event::MetadataUpdated { scope: MetadataScope::Collection(collection_id) })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Permanently freeze metadata of one token, or of all tokens
+    /// 
+    /// A frozen token's current URI is pinned, so later base URI changes do
+    /// not affect it. Freezing all tokens also locks the base URIs.
+    /// This cannot be undone.
+    /// 
+    /// # Arguments
+    /// * `token_id` - Token to freeze, or None to freeze everything
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If token_id does not exist
+    /// - If the token has no URI to pin (no explicit URI and no base URI)
+    #[export]
+    pub fn freeze_metadata(&mut self, token_id: Option<u64>) -> bool {
+        self.ensure_admin();
+
+        let scope = match token_id {
+            Some(token_id) => {
+                if !Storage::get().owners.contains_key(&token_id) {
+                    panic!("Token does not exist: {}", token_id);
+                }
+                let pinned_uri = resolve_token_uri(token_id)
+                    .unwrap_or_else(|| panic!("Token has no metadata URI to freeze: {}", token_id));
+                let storage = Storage::get_mut();
+                storage.token_uris.insert(token_id, pinned_uri);
+                storage.frozen_tokens.insert(token_id);
+                MetadataScope::Token(token_id)
+            }
+            None => {
+                Storage::get_mut().metadata_frozen = true;
+                MetadataScope::All
+            }
+        };
+
+        self.emit_event(Event::MetadataFrozen { scope })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Set backend signer sr25519 public key for mint vouchers
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    #[export]
+    pub fn set_backend_signer(&mut self, pubkey: [u8; 32]) -> bool {
+        self.ensure_admin();
+
+        Storage::get_mut().backend_signer = Some(pubkey);
+
+        self.emit_event(Event::BackendSignerUpdated { pubkey })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Create a hidden metadata batch for a mystery drop
+    /// 
+    /// # Arguments
+    /// * `placeholder_uri` - URI shown for every token until reveal
+    /// * `commitment` - blake2b-256 of the SCALE-encoded `RevealData`
+    /// * `size` - Number of slots (length of the committed URI list)
+    /// 
+    /// # Returns
+    /// The new batch ID
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If size is zero or exceeds `MAX_HIDDEN_BATCH_SIZE`
+    #[export]
+    pub fn create_hidden_batch(
+        &mut self,
+        placeholder_uri: String,
+        commitment: [u8; 32],
+        size: u32,
+    ) -> u64 {
+        self.ensure_admin();
+
+        if size == 0 || size > MAX_HIDDEN_BATCH_SIZE {
+            panic!("Batch size must be between 1 and {}", MAX_HIDDEN_BATCH_SIZE);
+        }
+
+        let storage = Storage::get_mut();
+        storage.last_batch_id = storage.last_batch_id
+            .checked_add(1)
+            .expect("Batch ID overflow");
+        let batch_id = storage.last_batch_id;
+
+        storage.hidden_batches.insert(batch_id, HiddenBatch {
+            id: batch_id,
+            placeholder_uri,
+            commitment,
+            size,
+            token_ids: Vec::new(),
+            reveal_offset: None,
+            revealed: false,
+        });
+
+        self.emit_event(Event::HiddenBatchCreated { batch_id, commitment, size })
+            .expect("Notification Error");
+
+        batch_id
+    }
+
+    /// Mint the next slot of a hidden batch with its placeholder URI
+    /// 
+    /// # Returns
+    /// The assigned token ID
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If the batch does not exist, is full or already closed
+    /// - If `to` is the zero address
+    #[export]
+    pub fn mint_hidden(&mut self, batch_id: u64, to: ActorId) -> u64 {
+        self.ensure_admin();
+
+        let batch = Storage::get().hidden_batches.get(&batch_id)
+            .unwrap_or_else(|| panic!("Batch does not exist: {}", batch_id));
+        if batch.reveal_offset.is_some() {
+            panic!("Batch is closed: {}", batch_id);
+        }
+        if batch.token_ids.len() as u32 >= batch.size {
+            panic!("Batch is full: {}", batch_id);
+        }
+
+        let token_id = mint_next_token(to, batch.placeholder_uri.clone());
+        Storage::get_mut().hidden_batches
+            .get_mut(&batch_id)
+            .expect("Batch existence checked above")
+            .token_ids
+            .push(token_id);
+
+        self.emit_event(Event::Transfer {
+            from: ActorId::zero(),
+            to,
+            token_id,
+        }).expect("Notification Error");
+
+        token_id
+    }
+
+    /// Close a hidden batch to further mints and fix its reveal offset
+    /// 
+    /// The offset is drawn from on-chain randomness only now, so whoever
+    /// prepared the committed URI list cannot tell which minter gets which URI.
+    /// 
+    /// # Returns
+    /// The reveal offset
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If the batch does not exist or is already closed
+    #[export]
+    pub fn close_hidden_batch(&mut self, batch_id: u64) -> u32 {
+        self.ensure_admin();
+
+        let batch = Storage::get_mut().hidden_batches.get_mut(&batch_id)
+            .unwrap_or_else(|| panic!("Batch does not exist: {}", batch_id));
+        if batch.reveal_offset.is_some() {
+            panic!("Batch already closed: {}", batch_id);
+        }
+
+        let (seed, _) = exec::random(batch.commitment).expect("Failed to get randomness");
+        let reveal_offset = funcs::compute_reveal_offset(&seed, batch.size);
+        batch.reveal_offset = Some(reveal_offset);
+
+        self.emit_event(Event::HiddenBatchClosed { batch_id, reveal_offset })
+            .expect("Notification Error");
+
+        reveal_offset
+    }
+
+    /// Reveal the real metadata of a hidden batch
+    /// 
+    /// Verifies `reveal_data` against the batch commitment, then gives the
+    /// token minted at position `i` the URI of slot `(i + offset) % size`,
+    /// using the offset fixed when the batch was closed. Burned tokens and
+    /// tokens whose metadata was frozen individually are skipped.
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If the batch does not exist, is not closed or is already revealed
+    /// - If all metadata is frozen
+    /// - If the URI count differs from the batch size
+    /// - If the hash does not match the commitment
+    #[export]
+    pub fn reveal(&mut self, batch_id: u64, reveal_data: RevealData) -> bool {
+        self.ensure_admin();
+
+        let storage = Storage::get_mut();
+        if storage.metadata_frozen {
+            panic!("Metadata is frozen");
+        }
+        let batch = storage.hidden_batches.get_mut(&batch_id)
+            .unwrap_or_else(|| panic!("Batch does not exist: {}", batch_id));
+        let reveal_offset = batch.reveal_offset
+            .unwrap_or_else(|| panic!("Batch is not closed: {}", batch_id));
+        if batch.revealed {
+            panic!("Batch already revealed: {}", batch_id);
+        }
+        if reveal_data.uris.len() != batch.size as usize {
+            panic!("Reveal data must contain {} URIs", batch.size);
+        }
+        if funcs::compute_reveal_hash(&reveal_data.salt, &reveal_data.uris) != batch.commitment {
+            panic!("Reveal data does not match commitment");
+        }
+
+        batch.revealed = true;
+        let mut updated = Vec::new();
+        for (index, token_id) in batch.token_ids.iter().enumerate() {
+            if storage.owners.contains_key(token_id) && !storage.frozen_tokens.contains(token_id) {
+                let slot = (index + reveal_offset as usize) % batch.size as usize;
+                storage.token_uris.insert(*token_id, reveal_data.uris[slot].clone());
+                updated.push(*token_id);
+            }
+        }
+        let token_count = batch.token_ids.len() as u32;
+
+        for token_id in updated {
+            self.emit_event(Event::MetadataUpdated { scope: MetadataScope::Token(token_id) })
+                .expect("Notification Error");
+        }
+        self.emit_event(Event::BatchRevealed { batch_id, token_count })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Transfer an NFT from one address to another
+    /// 
+    /// # Arguments
+    /// * `from` - Current owner
+    /// * `to` - New owner
+    /// * `token_id` - Token to transfer
+    /// 
+    /// # Panics
+    /// - If caller is not an admin, the owner, the approved account or an operator
+    /// - If token_id does not exist
+    /// - If from is not the current owner
+    #[export]
+    pub fn transfer_from(&mut self, from: ActorId, to: ActorId, token_id: u64) -> bool {
+        ensure_can_transfer(msg::source(), from, token_id);
+        
+        let moved_children = transfer_token(from, to, token_id);
+        
+        for token_id in core::iter::once(token_id).chain(moved_children) {
+            self.emit_event(Event::Transfer {
+                from,
+                to,
+                token_id,
+            }).expect("Notification Error");
+        }
+        
+        true
+    }
+
+    /// Transfer an NFT to a program and notify it
+    /// 
+    /// Sends `NftReceiver/OnNftReceived(operator, from, token_id, data)` to
+    /// `to`. If the receiver replies with anything but `true`, fails, or does
+    /// not reply within `REPLY_TIMEOUT_BLOCKS`, the transfer is rolled back,
+    /// `SafeTransferRejected` is emitted and false is returned. The rollback
+    /// restores the approvals, users and lock of the token and its nested
+    /// children. The token cannot be moved while the reply is pending.
+    /// Use `transfer_from` for user accounts, which do not reply.
+    /// 
+    /// # Panics
+    /// - Same conditions as `transfer_from`
+    #[export]
+    pub async fn safe_transfer_from(
+        &mut self,
+        from: ActorId,
+        to: ActorId,
+        token_id: u64,
+        data: Vec<u8>,
+    ) -> bool {
+        let operator = msg::source();
+        ensure_can_transfer(operator, from, token_id);
+
+        let previous_state = snapshot_moved_state(token_id);
+        let moved_children = transfer_token(from, to, token_id);
+        // Lock token BEFORE the async call
+        mark_pending(token_id);
+
+        let accepted = ReceiverClient::new(to)
+            .on_nft_received(operator, from, token_id, data)
+            .await;
+
+        Storage::get_mut().pending_tokens.remove(&token_id);
+
+        if accepted == Ok(true) {
+            for token_id in core::iter::once(token_id).chain(moved_children) {
+                self.emit_event(Event::Transfer {
+                    from,
+                    to,
+                    token_id,
+                }).expect("Notification Error");
+            }
+            return true;
+        }
+
+        // Roll back: return the token (with its children) and restore their
+        // approvals, users and locks
+        move_token(token_id, from);
+        restore_moved_state(previous_state);
+
+        self.emit_event(Event::SafeTransferRejected {
+            from,
+            to,
+            token_id,
+        }).expect("Notification Error");
+
+        false
+    }
+
+    /// Add a new admin
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    #[export]
+    pub fn add_admin(&mut self, admin: ActorId) -> bool {
+        self.ensure_admin();
+        
+        Storage::get_mut().admins.insert(admin);
+        
+        self.emit_event(Event::AdminAdded { admin })
+            .expect("Notification Error");
+        
+        true
+    }
+
+    /// Remove an admin
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    #[export]
+    pub fn remove_admin(&mut self, admin: ActorId) -> bool {
+        self.ensure_admin();
+        
+        Storage::get_mut().admins.remove(&admin);
+        
+        self.emit_event(Event::AdminRemoved { admin })
+            .expect("Notification Error");
+        
+        true
+    }
+
+    /// Clear the pending flag of a token whose reply wait has ended
+    /// 
+    /// Escape hatch for a cross-program call that never resumed, e.g. because
+    /// the waiting message ran out of gas. Nothing is rolled back: the token
+    /// stays with its current owner.
+    /// 
+    /// # Panics
+    /// - If caller is not an admin
+    /// - If the token is not pending or its reply wait has not ended yet
+    #[export]
+    pub fn clear_pending(&mut self, token_id: u64) -> bool {
+        self.ensure_admin();
+
+        let storage = Storage::get_mut();
+        let deadline = *storage.pending_tokens.get(&token_id)
+            .unwrap_or_else(|| panic!("Token is not pending: {}", token_id));
+        if exec::block_height() <= deadline {
+            panic!("Token operation in progress: {}", token_id);
+        }
+        storage.pending_tokens.remove(&token_id);
+
+        self.emit_event(Event::PendingCleared { token_id })
+            .expect("Notification Error");
+
+        true
+    }
+
+    // =========================================================================
+    // LOCKER FUNCTIONS
+    // =========================================================================
+
+    /// Lock a token in place
+    /// 
+    /// While locked, the token stays with its owner but only the locker can
+    /// transfer it; transferring clears the lock.
+    /// 
+    /// # Arguments
+    /// * `token_id` - Token to lock
+    /// * `lock_id` - Locker-defined ID (e.g. listing or stake ID)
+    /// 
+    /// # Panics
+    /// - If caller is not a registered locker
+    /// - If token_id does not exist or is already locked
+    /// - If caller is not the owner, the approved account or an operator
+    #[export]
+    pub fn lock(&mut self, token_id: u64, lock_id: u64) -> bool {
+        let locker = msg::source();
+        let storage = Storage::get_mut();
+        if !storage.lockers.contains(&locker) {
+            panic!("Not locker: caller is not a registered locker");
+        }
+        if !storage.owners.contains_key(&token_id) {
+            panic!("Token does not exist: {}", token_id);
+        }
+        if storage.token_locks.contains_key(&token_id) {
+            panic!("Token already locked: {}", token_id);
+        }
+        ensure_not_nested(token_id);
+        if !funcs::is_approved_or_owner(
+            &storage.owners,
+            &storage.token_approvals,
+            &storage.operator_approvals,
+            locker,
+            token_id,
+        ) {
+            panic!("Not authorized: locker is not owner, approved or operator");
+        }
+
+        storage.token_locks.insert(token_id, TokenLock { locker, lock_id });
+
+        self.emit_event(Event::Locked { token_id, locker, lock_id })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Release a lock
+    /// 
+    /// # Panics
+    /// - If token is not locked
+    /// - If caller is not the locker (with a matching lock_id) or an admin
+    #[export]
+    pub fn unlock(&mut self, token_id: u64, lock_id: u64) -> bool {
+        let caller = msg::source();
+        let storage = Storage::get_mut();
+        let lock = *storage.token_locks.get(&token_id)
+            .unwrap_or_else(|| panic!("Token is not locked: {}", token_id));
+
+        if !storage.admins.contains(&caller) {
+            if caller != lock.locker {
+                panic!("Not authorized: caller is not locker or admin");
+            }
+            if lock_id != lock.lock_id {
+                panic!("Lock ID mismatch");
+            }
+        }
+
+        storage.token_locks.remove(&token_id);
+
+        self.emit_event(Event::Unlocked {
+            token_id,
+            locker: lock.locker,
+            lock_id: lock.lock_id,
+        }).expect("Notification Error");
+
+        true
+    }
+
+    // =========================================================================
+    // NESTING FUNCTIONS
+    // =========================================================================
+
+    /// Nest a child token under a parent token
+    /// 
+    /// A child from this program must have the same owner as the parent; it
+    /// then moves with the parent and cannot be transferred on its own.
+    /// A child from a whitelisted program is escrowed into this program via
+    /// its `Nft/TransferFrom` (this program must be approved for it there).
+    /// Nesting is one level deep: parents cannot be nested and children
+    /// cannot have children.
+    /// 
+    /// # Returns
+    /// false if a whitelisted program refused the escrow transfer or did not
+    /// reply within `REPLY_TIMEOUT_BLOCKS` (the parent is released either way)
+    /// 
+    /// # Panics
+    /// - If caller cannot transfer the parent (or a local child)
+    /// - If the child is already nested, has children, or is soulbound or locked
+    /// - If the parent is nested or already has `MAX_CHILDREN` children
+    /// - If the child's program is not this program or whitelisted
+    #[export]
+    pub async fn nest(&mut self, child: ChildToken, parent_id: u64) -> bool {
+        let caller = msg::source();
+        let storage = Storage::get();
+        let parent_owner = *storage.owners.get(&parent_id)
+            .unwrap_or_else(|| panic!("Token does not exist: {}", parent_id));
+        ensure_can_transfer(caller, parent_owner, parent_id);
+        ensure_not_pending(parent_id);
+        ensure_not_nested(parent_id);
+        if storage.parents.contains_key(&child) {
+            panic!("Child already nested: {}", child.token_id);
+        }
+        if storage.children.get(&parent_id).is_some_and(|children| children.len() >= MAX_CHILDREN) {
+            panic!("Too many children: maximum is {}", MAX_CHILDREN);
+        }
+
+        if child.program == exec::program_id() {
+            if child.token_id == parent_id {
+                panic!("Cannot nest a token under itself");
+            }
+            ensure_can_transfer(caller, parent_owner, child.token_id);
+            ensure_not_pending(child.token_id);
+            if storage.token_locks.contains_key(&child.token_id) {
+                panic!("Token is locked: {}", child.token_id);
+            }
+            if is_soulbound(child.token_id) {
+                panic!("Token is soulbound: {}", child.token_id);
+            }
+            if storage.children.contains_key(&child.token_id) {
+                panic!("Token has nested children: {}", child.token_id);
+            }
+        } else {
+            if !storage.nestable_programs.contains(&child.program) {
+                panic!("Program is not nestable: {}", child.program);
+            }
+
+            // Lock parent BEFORE the async call
+            mark_pending(parent_id);
+            let escrowed = NftClient::new(child.program)
+                .transfer_from(caller, exec::program_id(), child.token_id)
+                .await;
+            Storage::get_mut().pending_tokens.remove(&parent_id);
+
+            if escrowed != Ok(true) {
+                return false;
+            }
+        }
+
+        let storage = Storage::get_mut();
+        storage.children.entry(parent_id).or_default().push(child);
+        storage.parents.insert(child, parent_id);
+
+        self.emit_event(Event::Nested { parent_id, child })
+            .expect("Notification Error");
+
+        true
+    }
+
+    /// Remove a child from its parent
+    /// 
+    /// A child from this program stays with the parent's owner and becomes
+    /// transferable again. A child from a whitelisted program is sent to the
+    /// parent's owner via its `Nft/Transfer`.
+    /// 
+    /// # Returns
+    /// false if a whitelisted program refused the transfer or did not reply
+    /// within `REPLY_TIMEOUT_BLOCKS` (child stays nested)
+    /// 
+    /// # Panics
+    /// - If the child is not nested
+    /// - If caller cannot transfer the parent
+    #[export]
+    pub async fn unnest(&mut self, child: ChildToken) -> bool {
+        let caller = msg::source();
+        let storage = Storage::get();
+        let parent_id = *storage.parents.get(&child)
+            .unwrap_or_else(|| panic!("Child is not nested: {}", child.token_id));
+        let parent_owner = storage.owners[&parent_id];
+        ensure_can_transfer(caller, parent_owner, parent_id);
+        ensure_not_pending(parent_id);
+
+        remove_child(parent_id, child);
+
+        if child.program != exec::program_id() {
+            // Lock parent BEFORE the async call
+            mark_pending(parent_id);
+            let released = NftClient::new(child.program)
+                .transfer(parent_owner, child.token_id)
+                .await;
+            let storage = Storage::get_mut();
+            storage.pending_tokens.remove(&parent_id);
+
+            if released != Ok(true) {
+                // Restore the nesting so the child is not lost
+                storage.children.entry(parent_id).or_default().push(child);
+                storage.parents.insert(child, parent_id);
+                return false;
+            }
+        }
+
+        self.emit_event(Event::Unnested { parent_id, child })
+            .expect("Notification Error");
+
+        true
+    }
+
+    // =========================================================================
+    // VOUCHER FUNCTIONS
+    // =========================================================================
+
+    /// Redeem a backend-signed mint voucher (caller pays gas)
+    /// 
+    /// Only the voucher recipient can redeem it, as with withdrawals on the
+    /// LINE token.
+    /// 
+    /// # Arguments
+    /// * `voucher` - Voucher signed by the backend
+    /// * `metadata_uri` - Metadata URI whose blake2b-256 hash is in the voucher
+    /// * `signature` - sr25519 signature over the voucher hash (64 bytes)
+    /// 
+    /// # Returns
+    /// The minted token ID
+    /// 
+    /// # Panics
+    /// - If caller is not the voucher recipient
+    /// - If voucher has expired or was already redeemed
+    /// - If backend signer is not configured
+    /// - If metadata_uri does not match the voucher
+    /// - If signature is invalid
+    /// - If the token ID is taken or the collection is full or missing
+    #[export]
+    pub fn redeem_voucher(
+        &mut self,
+        voucher: MintVoucher,
+        metadata_uri: String,
+        signature: Vec<u8>,
+    ) -> u64 {
+        let storage = Storage::get();
+
+        // 1. Check the caller is the recipient the backend signed for
+        if msg::source() != voucher.recipient {
+            panic!("Not voucher recipient: only the recipient can redeem");
+        }
+
+        // 2. Check expiry
+        if exec::block_timestamp() > voucher.expiry {
+            panic!("Voucher expired");
+        }
+
+        // 3. Check voucher_id not used
+        if storage.used_vouchers.contains(&voucher.voucher_id) {
+            panic!("Voucher already used");
+        }
+
+        // 4. Get backend signer
+        let signer = storage.backend_signer
+            .expect("Backend signer not configured");
+
+        // 5. Check metadata matches what the backend signed
+        if voucher::hash_metadata_uri(&metadata_uri) != voucher.metadata_hash {
+            panic!("Metadata does not match voucher");
+        }
+
+        // 6. Verify signature over the voucher hash
+        let payload_hash = voucher::compute_voucher_hash(&voucher);
+        voucher::verify_signature(&payload_hash, &signature, &signer);
+
+        // 7. Mark voucher_id as used (BEFORE minting)
+        Storage::get_mut().used_vouchers.insert(voucher.voucher_id);
+
+        // 8. Mint to the voucher recipient
+        let to = voucher.recipient;
+        let token_id = match voucher.target {
+            VoucherTarget::Token(token_id) => {
+                mint_token(to, token_id, metadata_uri);
+                token_id
+            }
+            VoucherTarget::Collection(collection_id) => {
+                let token_id = mint_next_token(to, metadata_uri);
+                add_to_collection(collection_id, token_id);
+                token_id
+            }
+        };
+
+        // 9. Emit events
+        self.emit_event(Event::Transfer {
+            from: ActorId::zero(),
+            to,
+            token_id,
+        }).expect("Notification Error");
+        self.emit_event(Event::VoucherRedeemed {
+            voucher_id: voucher.voucher_id,
+            recipient: to,
+            token_id,
+        }).expect("Notification Error");
+
+        token_id
+    }
+
+    // =========================================================================
+    // OWNER FUNCTIONS
+    // =========================================================================
+
+    /// Transfer one of the caller's NFTs to another address
+    /// 
+    /// # Panics
+    /// - If token_id does not exist
+    /// - If caller is not the current owner
+    #[export]
+    pub fn transfer(&mut self, to: ActorId, token_id: u64) -> bool {
+        let from = msg::source();
+        ensure_can_transfer(from, from, token_id);
+
+        let moved_children = transfer_token(from, to, token_id);
+
+        for token_id in core::iter::once(token_id).chain(moved_children) {
+            self.emit_event(Event::Transfer {
+                from,
+                to,
+                token_id,
+            }).expect("Notification Error");
+        }
+
+        true
+    }
+
+    /// Burn an NFT
+    /// 
+    /// Removes the token's owner, metadata URI and approval. The token ID
+    /// cannot be minted again.
+    /// 
+    /// # Panics
+    /// - If token_id does not exist
+    /// - If caller is not an admin, the owner, the approved account or an operator
+    #[export]
+    pub fn burn(&mut self, token_id: u64) -> bool {
+        let caller = msg::source();
+        let storage = Storage::get_mut();
+        if !storage.owners.contains_key(&token_id) {
+            panic!("Token does not exist: {}", token_id);
+        }
+        ensure_not_pending(token_id);
+        if storage.token_locks.get(&token_id).is_some_and(|lock| lock.locker != caller) {
+            panic!("Token is locked: {}", token_id);
+        }
+        ensure_not_nested(token_id);
+        if storage.children.contains_key(&token_id) {
+            panic!("Token has nested children: {}", token_id);
+        }
+
+        if !storage.admins.contains(&caller)
+            && !funcs::is_approved_or_owner(
+                &storage.owners,
+                &storage.token_approvals,
+                &storage.operator_approvals,
+                caller,
+                token_id,
+            )
+        {
+            panic!("Not authorized: caller is not owner, approved or admin");
+        }
+
+        let from = funcs::burn(
+            &mut storage.owners,
+            &mut storage.tokens_by_owner,
+            &mut storage.token_uris,
+            &mut storage.token_approvals,
+            &mut storage.burned,
+            token_id,
+        )
+        .expect("Token existence checked above");
+        storage.token_ids.remove(&token_id);
+        vnft::emit_transfer(from, ActorId::zero(), token_id);
+        storage.token_metadata.remove(&token_id);
+        storage.token_collections.remove(&token_id);
+        storage.token_royalties.remove(&token_id);
+        storage.soulbound_tokens.remove(&token_id);
+        storage.frozen_tokens.remove(&token_id);
+        storage.token_users.remove(&token_id);
+        storage.token_locks.remove(&token_id);
+
+        // Emit Transfer event with to = zero address (indicating burn)
+        self.emit_event(Event::Transfer {
+            from,
+            to: ActorId::zero(),
+            token_id,
+        }).expect("Notification Error");
+
+        true
+    }
+
+    /// Approve an account to transfer a single token
+    /// 
+    /// Pass the zero address to clear the approval.
+    /// The approval is cleared automatically when the token is transferred.
+    /// 
+    /// # Panics
+    /// - If token_id does not exist
+    /// - If caller is not the owner or an operator of the owner
+    /// - If `to` is the current owner
+    #[export]
+    pub fn approve(&mut self, to: ActorId, token_id: u64) -> bool {
+        let owner = approve_token(msg::source(), to, token_id);
+
+        self.emit_event(Event::Approval {
+            owner,
+            approved: to,
+            token_id,
+        }).expect("Notification Error");
+
+        true
+    }
+
+    /// Lend usage rights of a token until an expiry time (ERC-4907)
+    /// 
+    /// Ownership does not change. The user is cleared when the token is
+    /// transferred. Pass the zero address to clear it early.
+    /// 
+    /// # Arguments
+    /// * `token_id` - Token to lend
+    /// * `user` - Account allowed to use the token
+    /// * `expires_ms` - Expiry timestamp in milliseconds
+    /// 
+    /// # Panics
+    /// - If token_id does not exist
+    /// - If caller is not the owner, the approved account or an operator
+    /// - If expires_ms is not in the future
+    #[export]
+    pub fn set_user(&mut self, token_id: u64, user: ActorId, expires_ms: u64) -> bool {
+        let caller = msg::source();
+        let storage = Storage::get_mut();
+        if !storage.owners.contains_key(&token_id) {
+            panic!("Token does not exist: {}", token_id);
+        }
+        if !funcs::is_approved_or_owner(
+            &storage.owners,
+            &storage.token_approvals,
+            &storage.operator_approvals,
+            caller,
+            token_id,
+        ) {
+            panic!("Not authorized: caller is not owner or approved");
+        }
+
+        if user.is_zero() {
+            storage.token_users.remove(&token_id);
+        } else {
+            if expires_ms <= exec::block_timestamp() {
+                panic!("Expiry must be in the future");
+            }
+            storage.token_users.insert(token_id, TokenUser { user, expires_ms });
+        }
+
+        self.emit_event(Event::UpdateUser {
+            token_id,
+            user,
+            expires_ms,
+        }).expect("Notification Error");
+
+        true
+    }
+
+    /// Approve or revoke an operator for all of the caller's tokens
+    /// 
+    /// # Panics
+    /// - If operator is the caller
+    #[export]
+    pub fn set_approval_for_all(&mut self, operator: ActorId, approved: bool) -> bool {
+        let owner = msg::source();
+        if operator == owner {
+            panic!("Cannot set approval for self");
+        }
+
+        let storage = Storage::get_mut();
+        if approved {
+            storage.operator_approvals.insert((owner, operator));
+        } else {
+            storage.operator_approvals.remove(&(owner, operator));
+        }
+
+        self.emit_event(Event::ApprovalForAll {
+            owner,
+            operator,
+            approved,
+        }).expect("Notification Error");
+
+        true
+    }
+
+    // =========================================================================
+    // QUERY FUNCTIONS
+    // =========================================================================
+
+    /// Get the account approved to transfer a token
+    #[export]
+    pub fn get_approved(&self, token_id: u64) -> Option<ActorId> {
+        Storage::get().token_approvals.get(&token_id).cloned()
+    }
+
+    /// Check if an operator is approved for all tokens of an owner
+    #[export]
+    pub fn is_approved_for_all(&self, owner: ActorId, operator: ActorId) -> bool {
+        Storage::get().operator_approvals.contains(&(owner, operator))
+    }
+
+    /// Get the owner of a token
+    #[export]
+    pub fn owner_of(&self, token_id: u64) -> Option<ActorId> {
+        Storage::get().owners.get(&token_id).cloned()
+    }
+
+    /// Get the metadata URI of a token
+    /// 
+    /// Returns the explicit URI if set, otherwise the collection or global
+    /// base URI followed by the token ID.
+    #[export]
+    pub fn token_uri(&self, token_id: u64) -> Option<String> {
+        resolve_token_uri(token_id)
+    }
+
+    /// Get the global base URI
+    #[export]
+    pub fn base_uri(&self) -> String {
+        Storage::get().base_uri.clone()
+    }
+
+    /// Check if a token's metadata is frozen
+    #[export]
+    pub fn is_metadata_frozen(&self, token_id: u64) -> bool {
+        let storage = Storage::get();
+        storage.metadata_frozen || storage.frozen_tokens.contains(&token_id)
+    }
+
+    /// Get the current user of a token
+    /// 
+    /// Returns None if no user is set or the rental has expired.
+    #[export]
+    pub fn user_of(&self, token_id: u64) -> Option<ActorId> {
+        Storage::get().token_users
+            .get(&token_id)
+            .filter(|info| info.expires_ms >= exec::block_timestamp())
+            .map(|info| info.user)
+    }
+
+    /// Get the rental expiry of a token (may be in the past)
+    #[export]
+    pub fn user_expires(&self, token_id: u64) -> Option<u64> {
+        Storage::get().token_users.get(&token_id).map(|info| info.expires_ms)
+    }
+
+    /// Get a hidden metadata batch by ID
+    #[export]
+    pub fn hidden_batch(&self, batch_id: u64) -> Option<HiddenBatch> {
+        Storage::get().hidden_batches.get(&batch_id).cloned()
+    }
+
+    /// Get the children nested under a token
+    #[export]
+    pub fn children_of(&self, token_id: u64) -> Vec<ChildToken> {
+        Storage::get().children.get(&token_id).cloned().unwrap_or_default()
+    }
+
+    /// Get the parent a token of this program is nested under
+    #[export]
+    pub fn parent_of(&self, token_id: u64) -> Option<u64> {
+        let child = ChildToken { program: exec::program_id(), token_id };
+        Storage::get().parents.get(&child).cloned()
+    }
+
+    /// Check if tokens of another NFT program can be nested
+    #[export]
+    pub fn is_nestable_program(&self, program: ActorId) -> bool {
+        Storage::get().nestable_programs.contains(&program)
+    }
+
+    /// Get the lock held on a token
+    #[export]
+    pub fn lock_of(&self, token_id: u64) -> Option<TokenLock> {
+        Storage::get().token_locks.get(&token_id).cloned()
+    }
+
+    /// Check if an account is a registered locker
+    #[export]
+    pub fn is_locker(&self, account: ActorId) -> bool {
+        Storage::get().lockers.contains(&account)
+    }
+
+    /// Get the structured metadata of a token, if it was minted with any
+    #[export]
+    pub fn token_metadata(&self, token_id: u64) -> Option<TokenMetadata> {
+        Storage::get().token_metadata.get(&token_id).cloned()
+    }
+
+    /// Get total number of NFTs minted
+    #[export]
+    pub fn total_supply(&self) -> u64 {
+        Storage::get().total_supply
+    }
+
+    /// Get number of NFTs burned
+    #[export]
+    pub fn burned_count(&self) -> u64 {
+        Storage::get().burned.len() as u64
+    }
+
+    /// Get number of NFTs currently in existence (minted minus burned)
+    #[export]
+    pub fn circulating_supply(&self) -> u64 {
+        Storage::get().owners.len() as u64
+    }
+
+    /// Check if a token ID has been burned
+    #[export]
+    pub fn is_burned(&self, token_id: u64) -> bool {
+        Storage::get().burned.contains(&token_id)
+    }
+
+    /// Get the number of tokens owned by an account
+    #[export]
+    pub fn balance_of(&self, owner: ActorId) -> u64 {
+        Storage::get().tokens_by_owner
+            .get(&owner)
+            .map_or(0, |tokens| tokens.len() as u64)
+    }
+
+    /// Get a page of token IDs owned by an account, in ascending order
+    /// 
+    /// At most `MAX_PAGE_SIZE` (100) items are returned per call.
+    #[export]
+    pub fn tokens_of_owner(&self, owner: ActorId, offset: u32, limit: u32) -> Vec<u64> {
+        let Some(tokens) = Storage::get().tokens_by_owner.get(&owner) else {
+            return Vec::new();
+        };
+        funcs::paginate(tokens.iter().copied(), offset, limit.min(MAX_PAGE_SIZE))
+    }
+
+    /// Get a page of all tokens with their owners, in ascending token ID order
+    /// 
+    /// At most `MAX_PAGE_SIZE` (100) items are returned per call.
+    #[export]
+    pub fn tokens(&self, offset: u32, limit: u32) -> Vec<(u64, ActorId)> {
+        let storage = Storage::get();
+        let tokens = storage.token_ids
+            .iter()
+            .map(|token_id| (*token_id, storage.owners[token_id]));
+        funcs::paginate(tokens, offset, limit.min(MAX_PAGE_SIZE))
+    }
+
+    /// Get all collections, in ascending ID order
+    #[export]
+    pub fn collections(&self) -> Vec<Collection> {
+        let mut collections: Vec<Collection> = Storage::get().collections
+            .values()
+            .cloned()
+            .collect();
+        collections.sort_unstable_by_key(|collection| collection.id);
+        collections
+    }
+
+    /// Get a collection by ID
+    #[export]
+    pub fn collection(&self, collection_id: u32) -> Option<Collection> {
+        Storage::get().collections.get(&collection_id).cloned()
+    }
+
+    /// Get the collection a token belongs to
+    #[export]
+    pub fn collection_of(&self, token_id: u64) -> Option<u32> {
+        Storage::get().token_collections.get(&token_id).cloned()
+    }
+
+    /// Check if an account can mint into a collection (admins always can)
+    #[export]
+    pub fn is_collection_minter(&self, collection_id: u32, account: ActorId) -> bool {
+        let storage = Storage::get();
+        storage.admins.contains(&account)
+            || storage.collection_minters.contains(&(collection_id, account))
+    }
+
+    /// Get the royalty owed on a sale (EIP-2981 style)
+    /// 
+    /// Uses the token royalty if set, otherwise the collection royalty.
+    /// Returns the zero address and zero amount if no royalty applies.
+    #[export]
+    pub fn royalty_info(&self, token_id: u64, sale_price: U256) -> (ActorId, U256) {
+        let storage = Storage::get();
+        let royalty = storage.token_royalties.get(&token_id).or_else(|| {
+            storage.token_collections
+                .get(&token_id)
+                .and_then(|collection_id| storage.collection_royalties.get(collection_id))
+        });
+
+        match royalty {
+            Some(royalty) => (
+                royalty.recipient,
+                funcs::calculate_royalty(sale_price, royalty.bps),
+            ),
+            None => (ActorId::zero(), U256::zero()),
+        }
+    }
+
+    /// Check if a token is soulbound, directly or through its collection
+    #[export]
+    pub fn is_soulbound(&self, token_id: u64) -> bool {
+        is_soulbound(token_id)
+    }
+
+    /// Get backend signer public key for mint vouchers
+    #[export]
+    pub fn backend_signer(&self) -> Option<[u8; 32]> {
+        Storage::get().backend_signer
+    }
+
+    /// Check if a token has a cross-program call in flight
+    #[export]
+    pub fn is_pending(&self, token_id: u64) -> bool {
+        Storage::get().pending_tokens.contains_key(&token_id)
+    }
+
+    /// Check if a mint voucher has been redeemed
+    #[export]
+    pub fn is_voucher_used(&self, voucher_id: [u8; 32]) -> bool {
+        Storage::get().used_vouchers.contains(&voucher_id)
+    }
+
+    /// Get all admins
+    #[export]
+    pub fn admins(&self) -> Vec<ActorId> {
+        Storage::get().admins.iter().cloned().collect()
+    }
+
+    /// Check if an account is an admin
+    #[export]
+    pub fn is_admin(&self, account: ActorId) -> bool {
+        Storage::get().admins.contains(&account)
+    }
+}
+
+impl NftService {
     fn ensure_admin(&self) {
         if !Storage::get().admins.contains(&msg::source()) {
             panic!("Not admin: only admin can perform this action");
         }
     }
+
+    fn ensure_collection_minter(&self, collection_id: u32) {
+        let caller = msg::source();
+        let storage = Storage::get();
+        if !storage.admins.contains(&caller)
+            && !storage.collection_minters.contains(&(collection_id, caller))
+        {
+            panic!("Not authorized: caller is not admin or collection minter");
+        }
+    }
+}
+
+fn ensure_collection_exists(collection_id: u32) {
+    if !Storage::get().collections.contains_key(&collection_id) {
+        panic!("Collection does not exist: {}", collection_id);
+    }
+}
+
+/// Resolve the metadata URI of an existing token
+fn resolve_token_uri(token_id: u64) -> Option<String> {
+    let storage = Storage::get();
+    if !storage.owners.contains_key(&token_id) {
+        return None;
+    }
+    let collection_base_uri = storage.token_collections
+        .get(&token_id)
+        .and_then(|collection_id| storage.collections.get(collection_id))
+        .map(|collection| collection.base_uri.as_str());
+
+    funcs::resolve_token_uri(
+        storage.token_uris.get(&token_id),
+        collection_base_uri,
+        &storage.base_uri,
+        token_id,
+    )
+}
+
+/// Check if a token is soulbound, directly or through its collection
+fn is_soulbound(token_id: u64) -> bool {
+    let storage = Storage::get();
+    storage.soulbound_tokens.contains(&token_id)
+        || storage.token_collections
+            .get(&token_id)
+            .and_then(|collection_id| storage.collections.get(collection_id))
+            .is_some_and(|collection| collection.soulbound)
+}
+
+/// Record a freshly minted token as part of a collection
+/// 
+/// Panics if the collection does not exist or its max supply is reached.
+fn add_to_collection(collection_id: u32, token_id: u64) {
+    let storage = Storage::get_mut();
+    let collection = storage.collections.get_mut(&collection_id)
+        .unwrap_or_else(|| panic!("Collection does not exist: {}", collection_id));
+    if collection.max_supply.is_some_and(|max| collection.minted >= max) {
+        panic!("Collection max supply reached: {}", collection_id);
+    }
+    collection.minted += 1;
+    storage.token_collections.insert(token_id, collection_id);
+}
+
+/// Validate and store a royalty (royalties are write-once)
+fn set_royalty<K: core::hash::Hash + Eq>(
+    royalties: &mut HashMap<K, Royalty>,
+    key: K,
+    recipient: ActorId,
+    bps: u32,
+) {
+    if royalties.contains_key(&key) {
+        panic!("Royalty already set");
+    }
+    if bps == 0 {
+        panic!("Royalty must be greater than zero");
+    }
+    if bps > MAX_ROYALTY_BPS {
+        panic!(
+            "Royalty cannot exceed {} bps ({}%)",
+            MAX_ROYALTY_BPS,
+            MAX_ROYALTY_BPS / 100
+        );
+    }
+    if recipient.is_zero() {
+        panic!("Royalty recipient cannot be zero address");
+    }
+
+    royalties.insert(key, Royalty { recipient, bps });
+}
+
+/// Mint a token after checking its recipient and that its ID is unused
+fn mint_token(to: ActorId, token_id: u64, metadata_uri: String) {
+    if to.is_zero() {
+        panic!("Cannot mint to zero address");
+    }
+    let storage = Storage::get_mut();
+    if storage.owners.contains_key(&token_id) {
+        panic!("Token ID already exists: {}", token_id);
+    }
+    if storage.burned.contains(&token_id) {
+        panic!("Token ID was burned: {}", token_id);
+    }
+
+    funcs::mint(
+        &mut storage.owners,
+        &mut storage.tokens_by_owner,
+        &mut storage.token_uris,
+        &mut storage.total_supply,
+        to,
+        token_id,
+        metadata_uri,
+    );
+    storage.token_ids.insert(token_id);
+    vnft::emit_transfer(ActorId::zero(), to, token_id);
+}
+
+/// Mint a token under the next free auto-assigned ID
+fn mint_next_token(to: ActorId, metadata_uri: String) -> u64 {
+    let storage = Storage::get_mut();
+    let token_id = funcs::next_token_id(&storage.owners, &storage.burned, storage.last_auto_token_id);
+    storage.last_auto_token_id = token_id;
+
+    mint_token(to, token_id, metadata_uri);
+    token_id
+}
+
+/// Approve `to` for a single token on behalf of `caller` (zero clears it)
+/// and emit the standard `Vnft` approval event
+/// 
+/// Returns the token owner
+pub(crate) fn approve_token(caller: ActorId, to: ActorId, token_id: u64) -> ActorId {
+    let storage = Storage::get_mut();
+    let owner = *storage.owners.get(&token_id)
+        .unwrap_or_else(|| panic!("Token does not exist: {}", token_id));
+
+    if caller != owner && !storage.operator_approvals.contains(&(owner, caller)) {
+        panic!("Not authorized: caller is not owner or operator");
+    }
+    if to == owner {
+        panic!("Cannot approve current owner");
+    }
+
+    if to.is_zero() {
+        storage.token_approvals.remove(&token_id);
+    } else {
+        storage.token_approvals.insert(token_id, to);
+    }
+    vnft::emit_approval(owner, to, token_id);
+    owner
+}
+
+/// Panic unless `from` owns the token and `caller` may move it
+/// 
+/// A locked token can only be moved by its locker. Otherwise the caller must
+/// be an admin, the owner, the approved account or an operator.
+pub(crate) fn ensure_can_transfer(caller: ActorId, from: ActorId, token_id: u64) {
+    let storage = Storage::get();
+    let current_owner = storage.owners.get(&token_id)
+        .unwrap_or_else(|| panic!("Token does not exist: {}", token_id));
+
+    if *current_owner != from {
+        panic!("Not owner: {} does not own token {}", from, token_id);
+    }
+    ensure_not_nested(token_id);
+
+    if let Some(lock) = storage.token_locks.get(&token_id) {
+        if caller != lock.locker {
+            panic!("Token is locked: {}", token_id);
+        }
+        return;
+    }
+
+    if !storage.admins.contains(&caller)
+        && !funcs::is_approved_or_owner(
+            &storage.owners,
+            &storage.token_approvals,
+            &storage.operator_approvals,
+            caller,
+            token_id,
+        )
+    {
+        panic!("Not authorized: caller is not owner, approved or admin");
+    }
+}
+
+/// Mark a token as pending until its cross-program call ends or times out
+fn mark_pending(token_id: u64) {
+    let deadline = exec::block_height().saturating_add(REPLY_TIMEOUT_BLOCKS);
+    Storage::get_mut().pending_tokens.insert(token_id, deadline);
+}
+
+/// Panic if the token has a cross-program call in flight
+fn ensure_not_pending(token_id: u64) {
+    if Storage::get().pending_tokens.contains_key(&token_id) {
+        panic!("Token operation in progress: {}", token_id);
+    }
+}
+
+/// Panic if the token is nested under a parent
+fn ensure_not_nested(token_id: u64) {
+    let child = ChildToken { program: exec::program_id(), token_id };
+    if Storage::get().parents.contains_key(&child) {
+        panic!("Token is nested: {}", token_id);
+    }
+}
+
+/// Remove a child from its parent's nesting records
+fn remove_child(parent_id: u64, child: ChildToken) {
+    let storage = Storage::get_mut();
+    storage.parents.remove(&child);
+    if let Some(children) = storage.children.get_mut(&parent_id) {
+        children.retain(|nested| *nested != child);
+        if children.is_empty() {
+            storage.children.remove(&parent_id);
+        }
+    }
+}
+
+/// Transfer a token after validating the move
+/// 
+/// Returns the IDs of nested children from this program that moved with it
+pub(crate) fn transfer_token(from: ActorId, to: ActorId, token_id: u64) -> Vec<u64> {
+    if to.is_zero() {
+        panic!("Cannot transfer to zero address");
+    }
+    if from == to {
+        panic!("Cannot transfer to current owner");
+    }
+    if is_soulbound(token_id) {
+        panic!("Token is soulbound: {}", token_id);
+    }
+    ensure_not_pending(token_id);
+    ensure_not_nested(token_id);
+
+    move_token(token_id, to)
+}
+
+/// Move a token and its nested children from this program to a new owner,
+/// clearing their approvals, users and locks
+/// 
+/// Emits the standard `Vnft` transfer event for each moved token.
+/// 
+/// Returns the IDs of the children that moved
+fn move_token(token_id: u64, to: ActorId) -> Vec<u64> {
+    let moved_children = local_children(token_id);
+    let storage = Storage::get_mut();
+
+    for id in core::iter::once(token_id).chain(moved_children.iter().copied()) {
+        let from = storage.owners[&id];
+        funcs::transfer(
+            &mut storage.owners,
+            &mut storage.tokens_by_owner,
+            &mut storage.token_approvals,
+            id,
+            to,
+        );
+        storage.token_users.remove(&id);
+        storage.token_locks.remove(&id);
+        vnft::emit_transfer(from, to, id);
+    }
+    moved_children
+}
+
+/// IDs of the children from this program nested under a token
+fn local_children(token_id: u64) -> Vec<u64> {
+    let program_id = exec::program_id();
+    Storage::get().children
+        .get(&token_id)
+        .map(|children| {
+            children.iter()
+                .filter(|child| child.program == program_id)
+                .map(|child| child.token_id)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Approval, user and lock of a token, as cleared by `move_token`
+struct MovedTokenState {
+    token_id: u64,
+    approval: Option<ActorId>,
+    user: Option<TokenUser>,
+    lock: Option<TokenLock>,
+}
+
+/// Capture the state `move_token` clears for a token and its local children
+fn snapshot_moved_state(token_id: u64) -> Vec<MovedTokenState> {
+    let storage = Storage::get();
+    core::iter::once(token_id)
+        .chain(local_children(token_id))
+        .map(|id| MovedTokenState {
+            token_id: id,
+            approval: storage.token_approvals.get(&id).copied(),
+            user: storage.token_users.get(&id).copied(),
+            lock: storage.token_locks.get(&id).copied(),
+        })
+        .collect()
+}
+
+/// Restore state captured by `snapshot_moved_state`
+fn restore_moved_state(states: Vec<MovedTokenState>) {
+    let storage = Storage::get_mut();
+    for state in states {
+        if let Some(approved) = state.approval {
+            storage.token_approvals.insert(state.token_id, approved);
+        }
+        if let Some(user) = state.user {
+            storage.token_users.insert(state.token_id, user);
+        }
+        if let Some(lock) = state.lock {
+            storage.token_locks.insert(state.token_id, lock);
+        }
+    }
 }
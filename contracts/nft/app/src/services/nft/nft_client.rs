@@ -0,0 +1,82 @@
+//! Client for moving tokens held in other NFT programs
+//!
+//! Used to escrow children from whitelisted NFT programs while nested.
+//! Targets the `Nft` service of programs built from this contract. A call
+//! with no reply within `REPLY_TIMEOUT_BLOCKS` fails.
+//!
+//! Uses sails-rs encoding format:
+//! - Service route (SCALE-encoded string)
+//! - Method route (SCALE-encoded string)
+//! - Params (SCALE-encoded as a TUPLE, not separate args)
+
+use gstd::msg;
+use sails_rs::prelude::*;
+
+/// NFT contract client for cross-contract calls
+pub struct NftClient {
+    program_id: ActorId,
+}
+
+impl NftClient {
+    pub fn new(program_id: ActorId) -> Self {
+        Self { program_id }
+    }
+
+    /// Transfer a token from one address to another
+    ///
+    /// Used for: escrowing a child when it is nested
+    /// Requires: this program is approved for the token or an operator of `from`
+    pub async fn transfer_from(&self, from: ActorId, to: ActorId, token_id: u64) -> Result<bool, String> {
+        let mut payload = Vec::new();
+        "Nft".encode_to(&mut payload);                // Service route
+        "TransferFrom".encode_to(&mut payload);       // Method route
+        (from, to, token_id).encode_to(&mut payload); // Params as tuple
+
+        let reply_bytes = msg::send_bytes_for_reply(self.program_id, &payload, 0, 0)
+            .map_err(|e| format!("Failed to send NFT transfer message: {e:?}"))?
+            .up_to(Some(super::REPLY_TIMEOUT_BLOCKS))
+            .map_err(|e| format!("Failed to set NFT transfer reply timeout: {e:?}"))?
+            .await
+            .map_err(|e| format!("NFT transfer reply failed: {e:?}"))?;
+
+        Self::decode_reply(&reply_bytes)
+    }
+
+    /// Transfer a token owned by this program
+    ///
+    /// Used for: releasing a child when it is unnested
+    pub async fn transfer(&self, to: ActorId, token_id: u64) -> Result<bool, String> {
+        let mut payload = Vec::new();
+        "Nft".encode_to(&mut payload);          // Service route
+        "Transfer".encode_to(&mut payload);     // Method route
+        (to, token_id).encode_to(&mut payload); // Params as tuple
+
+        let reply_bytes = msg::send_bytes_for_reply(self.program_id, &payload, 0, 0)
+            .map_err(|e| format!("Failed to send NFT transfer message: {e:?}"))?
+            .up_to(Some(super::REPLY_TIMEOUT_BLOCKS))
+            .map_err(|e| format!("Failed to set NFT transfer reply timeout: {e:?}"))?
+            .await
+            .map_err(|e| format!("NFT transfer reply failed: {e:?}"))?;
+
+        Self::decode_reply(&reply_bytes)
+    }
+
+    /// Decode reply, skipping the service/method prefix
+    fn decode_reply(reply_bytes: &[u8]) -> Result<bool, String> {
+        let mut cursor = reply_bytes;
+
+        // Skip service route
+        let _service: String = Decode::decode(&mut cursor)
+            .map_err(|e| format!("Failed to decode service route: {e:?}"))?;
+
+        // Skip method route
+        let _method: String = Decode::decode(&mut cursor)
+            .map_err(|e| format!("Failed to decode method route: {e:?}"))?;
+
+        // Decode result
+        let result: bool = Decode::decode(&mut cursor)
+            .map_err(|e| format!("Failed to decode result: {e:?}"))?;
+
+        Ok(result)
+    }
+}
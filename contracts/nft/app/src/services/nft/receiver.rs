@@ -0,0 +1,69 @@
+//! Client for notifying receiver programs of safe transfers
+//!
+//! Receiver programs expose an `NftReceiver` service with:
+//! `OnNftReceived(operator: ActorId, from: ActorId, token_id: u64, data: Vec<u8>) -> bool`
+//! and return `true` to accept the token.
+//!
+//! Uses sails-rs encoding format:
+//! - Service route (SCALE-encoded string)
+//! - Method route (SCALE-encoded string)
+//! - Params (SCALE-encoded as a TUPLE, not separate args)
+
+use gstd::msg;
+use sails_rs::prelude::*;
+
+/// Client for a program receiving an NFT
+pub struct ReceiverClient {
+    program_id: ActorId,
+}
+
+impl ReceiverClient {
+    pub fn new(program_id: ActorId) -> Self {
+        Self { program_id }
+    }
+
+    /// Notify the receiver that it was sent a token
+    ///
+    /// Returns Ok(true) only if the receiver accepted the token within
+    /// `REPLY_TIMEOUT_BLOCKS`
+    pub async fn on_nft_received(
+        &self,
+        operator: ActorId,
+        from: ActorId,
+        token_id: u64,
+        data: Vec<u8>,
+    ) -> Result<bool, String> {
+        let mut payload = Vec::new();
+        "NftReceiver".encode_to(&mut payload);                // Service route
+        "OnNftReceived".encode_to(&mut payload);              // Method route
+        (operator, from, token_id, data).encode_to(&mut payload); // Params as tuple
+
+        let reply_bytes = msg::send_bytes_for_reply(self.program_id, &payload, 0, 0)
+            .map_err(|e| format!("Failed to send OnNftReceived message: {e:?}"))?
+            .up_to(Some(super::REPLY_TIMEOUT_BLOCKS))
+            .map_err(|e| format!("Failed to set OnNftReceived reply timeout: {e:?}"))?
+            .await
+            .map_err(|e| format!("OnNftReceived reply failed: {e:?}"))?;
+
+        Self::decode_reply(&reply_bytes)
+    }
+
+    /// Decode reply, skipping the service/method prefix
+    fn decode_reply(reply_bytes: &[u8]) -> Result<bool, String> {
+        let mut cursor = reply_bytes;
+
+        // Skip service route
+        let _service: String = Decode::decode(&mut cursor)
+            .map_err(|e| format!("Failed to decode service route: {e:?}"))?;
+
+        // Skip method route
+        let _method: String = Decode::decode(&mut cursor)
+            .map_err(|e| format!("Failed to decode method route: {e:?}"))?;
+
+        // Decode result
+        let result: bool = Decode::decode(&mut cursor)
+            .map_err(|e| format!("Failed to decode result: {e:?}"))?;
+
+        Ok(result)
+    }
+}
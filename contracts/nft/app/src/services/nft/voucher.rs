@@ -0,0 +1,163 @@
+//! Backend-signed mint vouchers (lazy minting)
+//!
+//! The backend signs a blake2b-256 hash of the voucher with its sr25519 key,
+//! the same way `LineTokenService::withdraw` authorizations are signed.
+//! Payload layout (all integers big-endian):
+//! `domain || recipient (32) || target tag (1) || target id (8 or 4)
+//!  || metadata_hash (32) || expiry (8) || voucher_id (32)`
+
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use sails_rs::prelude::*;
+use schnorrkel::signing_context;
+
+/// Domain separator for mint voucher signatures
+const VOUCHER_DOMAIN: &[u8] = b"LINE_NFT_VOUCHER_V1";
+
+/// Signing context for schnorrkel (must match @polkadot/util-crypto)
+const SIGNING_CTX: &[u8] = b"substrate";
+
+/// Blake2b with 256-bit output (matches @polkadot/util-crypto blake2AsU8a)
+type Blake2b256 = Blake2b<U32>;
+
+/// What a voucher mints
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub enum VoucherTarget {
+    /// A specific token ID
+    Token(u64),
+    /// The next free token ID in a collection
+    Collection(u32),
+}
+
+/// Backend-issued authorization to mint one token
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub struct MintVoucher {
+    /// Account receiving the token
+    pub recipient: ActorId,
+    /// Token ID or collection to mint
+    pub target: VoucherTarget,
+    /// blake2b-256 hash of the metadata URI
+    pub metadata_hash: [u8; 32],
+    /// Expiry timestamp in milliseconds
+    pub expiry: u64,
+    /// Unique voucher ID (replay protection)
+    pub voucher_id: [u8; 32],
+}
+
+/// Compute blake2b-256 hash of a metadata URI
+pub fn hash_metadata_uri(metadata_uri: &str) -> [u8; 32] {
+    Blake2b256::digest(metadata_uri.as_bytes()).into()
+}
+
+/// Compute blake2b-256 hash of the voucher payload
+pub fn compute_voucher_hash(voucher: &MintVoucher) -> [u8; 32] {
+    let mut hasher = Blake2b256::new();
+
+    hasher.update(VOUCHER_DOMAIN);
+    hasher.update(voucher.recipient.as_ref());
+    match voucher.target {
+        VoucherTarget::Token(token_id) => {
+            hasher.update([0u8]);
+            hasher.update(token_id.to_be_bytes());
+        }
+        VoucherTarget::Collection(collection_id) => {
+            hasher.update([1u8]);
+            hasher.update(collection_id.to_be_bytes());
+        }
+    }
+    hasher.update(voucher.metadata_hash);
+    hasher.update(voucher.expiry.to_be_bytes());
+    hasher.update(voucher.voucher_id);
+
+    hasher.finalize().into()
+}
+
+/// Verify an sr25519 signature over a voucher hash
+pub fn verify_signature(message_hash: &[u8; 32], signature_bytes: &[u8], pubkey_bytes: &[u8; 32]) {
+    if signature_bytes.len() != 64 {
+        panic!("Invalid signature length: expected 64 bytes");
+    }
+    let mut sig_array = [0u8; 64];
+    sig_array.copy_from_slice(signature_bytes);
+
+    let signature = schnorrkel::Signature::from_bytes(&sig_array)
+        .expect("Invalid signature format");
+
+    let public_key = schnorrkel::PublicKey::from_bytes(pubkey_bytes)
+        .expect("Invalid public key format");
+
+    let ctx = signing_context(SIGNING_CTX);
+
+    if public_key.verify(ctx.bytes(message_hash), &signature).is_err() {
+        panic!("Invalid signature: verification failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Voucher: recipient 0x11.., collection 7, "ipfs://voucher1", expiry 1_700_000_000_000, id 0x22..
+    const METADATA_HASH: &str = "7b65e6d321300198618fe833aa0fb9dae7d79a1d6a82473d9ebec1a816b067ad";
+    const VOUCHER_HASH: &str = "2cd23a1c31926a64001d288e438d8e365119990666149f5d9db5a73ec9c66a2a";
+
+    // sr25519 keypair from mini secret [0x01; 32]
+    const SR25519_PUBKEY: &str = "189dac29296d31814dc8c56cf3d36a0543372bba7538fa322a4aebfebc39e056";
+    const SR25519_SIG: &str = "ca7e86decb53f993c351c3537762c562725950ae064b62771526d0033a20f1470f7f5d549bde05dc7aca8a3f3cebe03a5ce96fc3d945ed60448ac3409fa1368c";
+
+    fn hex<const N: usize>(s: &str) -> [u8; N] {
+        let mut out = [0u8; N];
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).unwrap();
+        }
+        out
+    }
+
+    fn voucher() -> MintVoucher {
+        MintVoucher {
+            recipient: ActorId::from([0x11u8; 32]),
+            target: VoucherTarget::Collection(7),
+            metadata_hash: hash_metadata_uri("ipfs://voucher1"),
+            expiry: 1_700_000_000_000,
+            voucher_id: [0x22u8; 32],
+        }
+    }
+
+    #[test]
+    fn test_hashes_match_vectors() {
+        assert_eq!(hash_metadata_uri("ipfs://voucher1"), hex::<32>(METADATA_HASH));
+        assert_eq!(compute_voucher_hash(&voucher()), hex::<32>(VOUCHER_HASH));
+    }
+
+    #[test]
+    fn test_target_is_part_of_hash() {
+        let mut other = voucher();
+        other.target = VoucherTarget::Token(7);
+        assert_ne!(compute_voucher_hash(&other), compute_voucher_hash(&voucher()));
+    }
+
+    #[test]
+    fn test_sr25519_vector_verifies() {
+        verify_signature(
+            &compute_voucher_hash(&voucher()),
+            &hex::<64>(SR25519_SIG),
+            &hex(SR25519_PUBKEY),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid signature")]
+    fn test_rejects_tampered_voucher() {
+        let mut tampered = voucher();
+        tampered.recipient = ActorId::from([0x33u8; 32]);
+        verify_signature(
+            &compute_voucher_hash(&tampered),
+            &hex::<64>(SR25519_SIG),
+            &hex(SR25519_PUBKEY),
+        );
+    }
+}
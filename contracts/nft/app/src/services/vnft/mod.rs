@@ -0,0 +1,137 @@
+//! VNFT Service - Standard Vara Non-Fungible Token interface
+//!
+//! Exposes the standard `Vnft` service (route names, method set and events)
+//! over the same storage as `NftService`, so Vara wallets and explorers can
+//! show our items without a custom IDL. Token IDs are `U256` in the standard
+//! and must fit in `u64` here.
+//! Minting, collections and other admin functions stay on the `Nft` service.
+//! Standard events are emitted from the shared mint, transfer, burn and
+//! approval paths, so explorers following `Vnft` see every token movement,
+//! including those made through the `Nft` service or the marketplace.
+
+use sails_rs::{
+    gstd::{msg, service, EventEmitter},
+    prelude::*,
+};
+
+use super::nft::{self, Storage};
+
+/// SCALE-encoded `Vnft` service route, as exposed by `NftProgram::vnft`
+const ROUTE: &[u8] = &[4 << 2, b'V', b'n', b'f', b't'];
+
+/// Standard VNFT events
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub enum Event {
+    /// Token transferred
+    Transfer {
+        from: ActorId,
+        to: ActorId,
+        token_id: U256,
+    },
+    /// Account approved to transfer a single token (zero clears the approval)
+    Approval {
+        owner: ActorId,
+        approved: ActorId,
+        token_id: U256,
+    },
+}
+
+/// Standard VNFT Service
+#[derive(Clone)]
+pub struct VnftService;
+
+impl VnftService {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[service(events = Event)]
+impl VnftService {
+    /// Approve an account to transfer one of the caller's tokens
+    #[export]
+    pub fn approve(&mut self, approved: ActorId, token_id: U256) {
+        nft::approve_token(msg::source(), approved, to_token_id(token_id));
+    }
+
+    /// Transfer one of the caller's tokens to another account
+    #[export]
+    pub fn transfer(&mut self, to: ActorId, token_id: U256) {
+        let from = msg::source();
+        let id = to_token_id(token_id);
+        nft::ensure_can_transfer(from, from, id);
+        nft::transfer_token(from, to, id);
+    }
+
+    /// Transfer a token as its owner, approved account, operator or admin
+    #[export]
+    pub fn transfer_from(&mut self, from: ActorId, to: ActorId, token_id: U256) {
+        let id = to_token_id(token_id);
+        nft::ensure_can_transfer(msg::source(), from, id);
+        nft::transfer_token(from, to, id);
+    }
+
+    /// Get the number of tokens owned by an account
+    #[export]
+    pub fn balance_of(&self, owner: ActorId) -> U256 {
+        Storage::get().tokens_by_owner
+            .get(&owner)
+            .map_or(0, |tokens| tokens.len())
+            .into()
+    }
+
+    /// Get the account approved for a token (zero if none)
+    #[export]
+    pub fn get_approved(&self, token_id: U256) -> ActorId {
+        Storage::get().token_approvals
+            .get(&to_token_id(token_id))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Get the owner of a token (zero if it does not exist)
+    #[export]
+    pub fn owner_of(&self, token_id: U256) -> ActorId {
+        Storage::get().owners
+            .get(&to_token_id(token_id))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Get the program name
+    #[export]
+    pub fn name(&self) -> String {
+        Storage::get().name.clone()
+    }
+
+    /// Get the program symbol
+    #[export]
+    pub fn symbol(&self) -> String {
+        Storage::get().symbol.clone()
+    }
+}
+
+/// Convert a standard `U256` token ID to the internal `u64` ID
+fn to_token_id(token_id: U256) -> u64 {
+    if token_id > U256::from(u64::MAX) {
+        panic!("Token ID out of range: {}", token_id);
+    }
+    token_id.low_u64()
+}
+
+/// Emit a standard `Transfer` event (`from` is zero on mint, `to` on burn)
+pub(crate) fn emit_transfer(from: ActorId, to: ActorId, token_id: u64) {
+    EventEmitter::<Event>::new(ROUTE)
+        .emit_event(Event::Transfer { from, to, token_id: token_id.into() })
+        .expect("Notification Error");
+}
+
+/// Emit a standard `Approval` event
+pub(crate) fn emit_approval(owner: ActorId, approved: ActorId, token_id: u64) {
+    EventEmitter::<Event>::new(ROUTE)
+        .emit_event(Event::Approval { owner, approved, token_id: token_id.into() })
+        .expect("Notification Error");
+}
@@ -0,0 +1,1035 @@
+//! NFT program tests
+//!
+//! Runs the compiled program in gtest and drives it with raw Sails messages
+//! (service route, method route, params as a tuple). Methods are named
+//! `Service/Method`, e.g. `Nft/Mint`.
+
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use sails_rs::{
+    gtest::{
+        BlockRunResult, Program, System, DEFAULT_USER_ALICE, DEFAULT_USER_BOB,
+        DEFAULT_USER_CHARLIE, DEFAULT_USER_EVE,
+    },
+    prelude::*,
+};
+
+const PROGRAM_ID: u64 = 100;
+const ADMIN: u64 = DEFAULT_USER_ALICE;
+const USER: u64 = DEFAULT_USER_BOB;
+const OTHER: u64 = DEFAULT_USER_CHARLIE;
+const THIRD: u64 = DEFAULT_USER_EVE;
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+fn actor(id: u64) -> ActorId {
+    ActorId::from(id)
+}
+
+fn deploy(system: &System) -> Program<'_> {
+    deploy_with_id(system, PROGRAM_ID)
+}
+
+fn deploy_with_id(system: &System, id: u64) -> Program<'_> {
+    let program = Program::from_binary_with_id(system, id, nft::WASM_BINARY);
+    let mut payload = Vec::new();
+    "New".encode_to(&mut payload);
+    (None::<Vec<ActorId>>,).encode_to(&mut payload);
+    let message_id = program.send_bytes(ADMIN, payload);
+    assert!(system.run_next_block().succeed.contains(&message_id));
+    program
+}
+
+/// Send a `Service/Method` message and run the block processing it
+fn send(
+    system: &System,
+    program: &Program,
+    from: u64,
+    method: &str,
+    args: impl Encode,
+) -> (MessageId, BlockRunResult) {
+    let (service, method) = method.split_once('/').expect("Method must be Service/Method");
+    let mut payload = Vec::new();
+    service.encode_to(&mut payload);
+    method.encode_to(&mut payload);
+    args.encode_to(&mut payload);
+
+    let message_id = program.send_bytes(from, payload);
+    (message_id, system.run_next_block())
+}
+
+/// Run blocks until the reply to `message_id` arrives
+/// 
+/// Returns the reply payload (or the panic message) and the block it arrived
+/// in. Gives up after 100 blocks, well past the program's reply timeout.
+fn wait_reply(
+    system: &System,
+    message_id: MessageId,
+    mut result: BlockRunResult,
+) -> (Result<Vec<u8>, String>, BlockRunResult) {
+    let last_block = system.block_height() + 100;
+    loop {
+        if let Some(reply) = result.log().iter().find(|log| log.reply_to() == Some(message_id)) {
+            let payload = reply.payload().to_vec();
+            let reply = if reply.reply_code().is_some_and(|code| code.is_error()) {
+                Err(String::from_utf8_lossy(&payload).into_owned())
+            } else {
+                Ok(payload)
+            };
+            return (reply, result);
+        }
+        assert!(system.block_height() < last_block, "No reply to {message_id:?}");
+        result = system.run_next_block();
+    }
+}
+
+/// Send a message and return the decoded reply, or the panic message
+/// 
+/// Runs further blocks until the reply arrives, so async methods that wait
+/// on other programs complete.
+fn try_call<R: Decode>(
+    system: &System,
+    program: &Program,
+    from: u64,
+    method: &str,
+    args: impl Encode,
+) -> Result<R, String> {
+    let (message_id, result) = send(system, program, from, method, args);
+    let (reply, _) = wait_reply(system, message_id, result);
+    let payload = reply?;
+
+    let mut cursor = &payload[..];
+    // Methods returning `()` get an empty auto-reply
+    if cursor.is_empty() {
+        return Ok(R::decode(&mut cursor).expect("Failed to decode reply"));
+    }
+    let _service = String::decode(&mut cursor).expect("Failed to decode service route");
+    let _method = String::decode(&mut cursor).expect("Failed to decode method route");
+    Ok(R::decode(&mut cursor).expect("Failed to decode reply"))
+}
+
+fn call<R: Decode>(system: &System, program: &Program, from: u64, method: &str, args: impl Encode) -> R {
+    try_call(system, program, from, method, args)
+        .unwrap_or_else(|error| core::panic!("{method} failed: {error}"))
+}
+
+/// Send a message that must panic and return the panic message
+fn call_err(system: &System, program: &Program, from: u64, method: &str, args: impl Encode) -> String {
+    match try_call::<()>(system, program, from, method, args) {
+        Ok(()) => core::panic!("{method} did not panic"),
+        Err(error) => error,
+    }
+}
+
+fn mint(system: &System, program: &Program, to: u64, token_id: u64) {
+    let _: bool = call(system, program, ADMIN, "Nft/Mint", (actor(to), token_id, String::new()));
+}
+
+/// Decode the `Service/Event` events emitted in a block
+fn events<E: Decode>(result: &BlockRunResult, event: &str) -> Vec<E> {
+    let (service, event) = event.split_once('/').expect("Event must be Service/Event");
+    result
+        .log()
+        .iter()
+        .filter(|log| log.destination() == ActorId::zero())
+        .filter_map(|log| {
+            let mut cursor = log.payload();
+            let log_service = String::decode(&mut cursor).ok()?;
+            let log_event = String::decode(&mut cursor).ok()?;
+            (log_service == service && log_event == event)
+                .then(|| E::decode(&mut cursor).expect("Failed to decode event"))
+        })
+        .collect()
+}
+
+/// Decode the standard `Vnft` events named `event` emitted in a block
+fn vnft_events(result: &BlockRunResult, event: &str) -> Vec<(ActorId, ActorId, U256)> {
+    events(result, &format!("Vnft/{event}"))
+}
+
+// ============================================================================
+// ENUMERATION TESTS
+// ============================================================================
+
+#[test]
+fn test_tokens_pages_in_ascending_id_order() {
+    let system = System::new();
+    let program = deploy(&system);
+
+    for (owner, token_id) in [(USER, 5), (OTHER, 1), (USER, 3), (THIRD, 4), (OTHER, 2)] {
+        mint(&system, &program, owner, token_id);
+    }
+    let _: bool = call(&system, &program, ADMIN, "Nft/Burn", (3u64,));
+
+    let page: Vec<(u64, ActorId)> = call(&system, &program, USER, "Nft/Tokens", (0u32, 2u32));
+    assert_eq!(page, vec![(1, actor(OTHER)), (2, actor(OTHER))]);
+    let page: Vec<(u64, ActorId)> = call(&system, &program, USER, "Nft/Tokens", (2u32, 10u32));
+    assert_eq!(page, vec![(4, actor(THIRD)), (5, actor(USER))]);
+}
+
+// ============================================================================
+// ON-CHAIN METADATA TESTS
+// ============================================================================
+
+/// `TokenMetadata { name, description, image, rarity, collection, attributes }`,
+/// with `rarity` as its `Rarity` variant index
+type TokenMetadata = (String, Option<String>, String, u8, Option<String>, Vec<(String, String)>);
+
+fn token_metadata(attribute_count: usize) -> TokenMetadata {
+    let attributes = (0..attribute_count)
+        .map(|i| (format!("trait{i}"), format!("value{i}")))
+        .collect();
+    (
+        String::from("Sword"),
+        Some(String::from("A sharp sword")),
+        String::from("ipfs://sword.png"),
+        3, // Rarity::Legendary
+        Some(String::from("Armory")),
+        attributes,
+    )
+}
+
+#[test]
+fn test_minted_metadata_round_trips_until_burn() {
+    let system = System::new();
+    let program = deploy(&system);
+
+    let metadata = token_metadata(32);
+    let mint = (actor(USER), 1u64, String::from("ipfs://sword"), metadata.clone());
+    let _: bool = call(&system, &program, ADMIN, "Nft/MintWithMetadata", mint);
+
+    let stored: Option<TokenMetadata> = call(&system, &program, USER, "Nft/TokenMetadata", (1u64,));
+    assert_eq!(stored, Some(metadata));
+    let uri: Option<String> = call(&system, &program, USER, "Nft/TokenUri", (1u64,));
+    assert_eq!(uri.as_deref(), Some("ipfs://sword"));
+
+    let _: bool = call(&system, &program, USER, "Nft/Burn", (1u64,));
+    let stored: Option<TokenMetadata> = call(&system, &program, USER, "Nft/TokenMetadata", (1u64,));
+    assert_eq!(stored, None);
+}
+
+#[test]
+fn test_mint_with_too_many_attributes_is_rejected() {
+    let system = System::new();
+    let program = deploy(&system);
+
+    let mint = (actor(USER), 1u64, String::new(), token_metadata(33));
+    let error = call_err(&system, &program, ADMIN, "Nft/MintWithMetadata", mint);
+    assert!(error.contains("Too many attributes"), "{error}");
+
+    let owner: Option<ActorId> = call(&system, &program, USER, "Nft/OwnerOf", (1u64,));
+    assert_eq!(owner, None);
+
+    let mint = (actor(USER), 1u64, String::new(), token_metadata(1));
+    let error = call_err(&system, &program, USER, "Nft/MintWithMetadata", mint);
+    assert!(error.contains("Not admin"), "{error}");
+}
+
+// ============================================================================
+// COLLECTION TESTS
+// ============================================================================
+
+/// `Collection { id, name, symbol, max_supply, minted, base_uri, soulbound }`
+type Collection = (u32, String, String, Option<u64>, u64, String, bool);
+
+fn create_collection(system: &System, program: &Program, max_supply: Option<u64>) -> u32 {
+    let name = String::from("Season 1");
+    let collection = (name, String::from("S1"), max_supply, String::from("ipfs://s1/"));
+    call(system, program, ADMIN, "Nft/CreateCollection", collection)
+}
+
+#[test]
+fn test_collection_mints_respect_max_supply() {
+    let system = System::new();
+    let program = deploy(&system);
+    let collection_id = create_collection(&system, &program, Some(2));
+
+    for token_id in [10u64, 11] {
+        let mint = (collection_id, actor(USER), token_id, String::new());
+        let _: bool = call(&system, &program, ADMIN, "Nft/MintInCollection", mint);
+    }
+    let collection: Option<u32> = call(&system, &program, USER, "Nft/CollectionOf", (10u64,));
+    assert_eq!(collection, Some(collection_id));
+    let uri: Option<String> = call(&system, &program, USER, "Nft/TokenUri", (10u64,));
+    assert_eq!(uri.as_deref(), Some("ipfs://s1/10"));
+
+    let mint = (collection_id, actor(USER), 12u64, String::new());
+    let error = call_err(&system, &program, ADMIN, "Nft/MintInCollection", mint);
+    assert!(error.contains("Collection max supply reached"), "{error}");
+
+    // Burned tokens still count towards the max supply
+    let _: bool = call(&system, &program, USER, "Nft/Burn", (10u64,));
+    let mint = (collection_id, actor(USER), 12u64, String::new());
+    let error = call_err(&system, &program, ADMIN, "Nft/MintInCollection", mint);
+    assert!(error.contains("Collection max supply reached"), "{error}");
+
+    let collections: Vec<Collection> = call(&system, &program, USER, "Nft/Collections", ());
+    assert_eq!(collections.len(), 1);
+    let (id, _, _, max_supply, minted, _, _) = &collections[0];
+    assert_eq!((*id, *max_supply, *minted), (collection_id, Some(2), 2));
+    let owner: Option<ActorId> = call(&system, &program, USER, "Nft/OwnerOf", (12u64,));
+    assert_eq!(owner, None);
+}
+
+#[test]
+fn test_collection_minting_requires_admin_or_collection_minter() {
+    let system = System::new();
+    let program = deploy(&system);
+
+    let collection = (String::from("Rogue"), String::from("R"), None::<u64>, String::new());
+    let error = call_err(&system, &program, USER, "Nft/CreateCollection", collection);
+    assert!(error.contains("Not admin"), "{error}");
+
+    let season_one = create_collection(&system, &program, None);
+    let season_two = create_collection(&system, &program, None);
+    let mint = (season_one, actor(OTHER), 1u64, String::new());
+    let error = call_err(&system, &program, USER, "Nft/MintInCollection", mint.clone());
+    assert!(error.contains("Not authorized"), "{error}");
+
+    let error = call_err(&system, &program, USER, "Nft/AddCollectionMinter", (season_one, actor(USER)));
+    assert!(error.contains("Not admin"), "{error}");
+    let _: bool = call(&system, &program, ADMIN, "Nft/AddCollectionMinter", (season_one, actor(USER)));
+
+    // A collection minter can mint into its own collection only
+    let _: bool = call(&system, &program, USER, "Nft/MintInCollection", mint);
+    let mint = (season_two, actor(OTHER), 2u64, String::new());
+    let error = call_err(&system, &program, USER, "Nft/MintInCollection", mint);
+    assert!(error.contains("Not authorized"), "{error}");
+    let error = call_err(&system, &program, USER, "Nft/Mint", (actor(OTHER), 3u64, String::new()));
+    assert!(error.contains("Not admin"), "{error}");
+
+    let _: bool = call(&system, &program, ADMIN, "Nft/RemoveCollectionMinter", (season_one, actor(USER)));
+    let mint = (season_one, actor(OTHER), 4u64, String::new());
+    let error = call_err(&system, &program, USER, "Nft/MintInCollection", mint);
+    assert!(error.contains("Not authorized"), "{error}");
+}
+
+#[test]
+fn test_collection_of_is_cleared_on_burn() {
+    let system = System::new();
+    let program = deploy(&system);
+    let collection_id = create_collection(&system, &program, None);
+
+    let mint = (collection_id, actor(USER), 1u64, String::new());
+    let _: bool = call(&system, &program, ADMIN, "Nft/MintInCollection", mint);
+    let _: bool = call(&system, &program, USER, "Nft/Burn", (1u64,));
+
+    let collection: Option<u32> = call(&system, &program, USER, "Nft/CollectionOf", (1u64,));
+    assert_eq!(collection, None);
+}
+
+// ============================================================================
+// ROYALTY TESTS
+// ============================================================================
+
+#[test]
+fn test_token_royalty_cannot_be_changed_once_set() {
+    let system = System::new();
+    let program = deploy(&system);
+    mint(&system, &program, USER, 1);
+
+    let royalty = (1u64, actor(THIRD), 500u32);
+    let _: bool = call(&system, &program, ADMIN, "Nft/SetTokenRoyalty", royalty);
+
+    for (recipient, bps) in [(actor(ADMIN), 1000u32), (actor(THIRD), 0)] {
+        let royalty = (1u64, recipient, bps);
+        let error = call_err(&system, &program, ADMIN, "Nft/SetTokenRoyalty", royalty);
+        assert!(error.contains("Royalty already set"), "{error}");
+    }
+
+    let sale = (1u64, U256::from(10_000));
+    let info: (ActorId, U256) = call(&system, &program, USER, "Nft/RoyaltyInfo", sale);
+    assert_eq!(info, (actor(THIRD), U256::from(500)));
+}
+
+#[test]
+fn test_token_royalty_takes_precedence_over_collection_royalty() {
+    let system = System::new();
+    let program = deploy(&system);
+    let collection_id = create_collection(&system, &program, None);
+
+    let royalty = (collection_id, actor(ADMIN), 250u32);
+    let _: bool = call(&system, &program, ADMIN, "Nft/SetCollectionRoyalty", royalty);
+    for token_id in [1u64, 2] {
+        let mint = (collection_id, actor(USER), token_id, String::new());
+        let _: bool = call(&system, &program, ADMIN, "Nft/MintInCollection", mint);
+    }
+    let royalty = (2u64, actor(THIRD), 1000u32);
+    let _: bool = call(&system, &program, ADMIN, "Nft/SetTokenRoyalty", royalty);
+
+    let sale = (1u64, U256::from(10_000));
+    let info: (ActorId, U256) = call(&system, &program, USER, "Nft/RoyaltyInfo", sale);
+    assert_eq!(info, (actor(ADMIN), U256::from(250)));
+    let sale = (2u64, U256::from(10_000));
+    let info: (ActorId, U256) = call(&system, &program, USER, "Nft/RoyaltyInfo", sale);
+    assert_eq!(info, (actor(THIRD), U256::from(1000)));
+
+    // Tokens outside any collection without a royalty owe nothing
+    mint(&system, &program, USER, 3);
+    let sale = (3u64, U256::from(10_000));
+    let info: (ActorId, U256) = call(&system, &program, USER, "Nft/RoyaltyInfo", sale);
+    assert_eq!(info, (ActorId::zero(), U256::zero()));
+}
+
+#[test]
+fn test_royalty_is_capped_and_collection_royalty_precedes_minting() {
+    let system = System::new();
+    let program = deploy(&system);
+    mint(&system, &program, USER, 1);
+
+    let royalty = (1u64, actor(THIRD), 1001u32);
+    let error = call_err(&system, &program, ADMIN, "Nft/SetTokenRoyalty", royalty);
+    assert!(error.contains("Royalty cannot exceed 1000 bps"), "{error}");
+    let royalty = (1u64, actor(USER), 100u32);
+    let error = call_err(&system, &program, USER, "Nft/SetTokenRoyalty", royalty);
+    assert!(error.contains("Not admin"), "{error}");
+
+    let collection_id = create_collection(&system, &program, None);
+    let royalty = (collection_id, actor(THIRD), 1001u32);
+    let error = call_err(&system, &program, ADMIN, "Nft/SetCollectionRoyalty", royalty);
+    assert!(error.contains("Royalty cannot exceed 1000 bps"), "{error}");
+
+    let mint = (collection_id, actor(USER), 2u64, String::new());
+    let _: bool = call(&system, &program, ADMIN, "Nft/MintInCollection", mint);
+    let royalty = (collection_id, actor(THIRD), 500u32);
+    let error = call_err(&system, &program, ADMIN, "Nft/SetCollectionRoyalty", royalty);
+    assert!(error.contains("Collection already has minted tokens"), "{error}");
+}
+
+// ============================================================================
+// AUTO-ID MINT TESTS
+// ============================================================================
+
+#[test]
+fn test_mint_next_skips_explicitly_minted_ids() {
+    let system = System::new();
+    let program = deploy(&system);
+    mint(&system, &program, USER, 2);
+    mint(&system, &program, USER, 3);
+
+    let first: u64 = call(&system, &program, ADMIN, "Nft/MintNext", (actor(OTHER), String::new()));
+    let second: u64 = call(&system, &program, ADMIN, "Nft/MintNext", (actor(OTHER), String::new()));
+    assert_eq!((first, second), (1, 4));
+
+    // Burned IDs are never reassigned either
+    let _: bool = call(&system, &program, OTHER, "Nft/Burn", (4u64,));
+    mint(&system, &program, USER, 5);
+    let items = vec![(actor(THIRD), String::new()), (actor(OTHER), String::from("ipfs://7"))];
+    let (_, result) = send(&system, &program, ADMIN, "Nft/BatchMint", (items,));
+    assert_eq!(
+        vnft_events(&result, "Transfer"),
+        [
+            (ActorId::zero(), actor(THIRD), U256::from(6)),
+            (ActorId::zero(), actor(OTHER), U256::from(7)),
+        ]
+    );
+    let uri: Option<String> = call(&system, &program, USER, "Nft/TokenUri", (7u64,));
+    assert_eq!(uri.as_deref(), Some("ipfs://7"));
+}
+
+#[test]
+fn test_batch_mint_is_bounded_and_atomic() {
+    let system = System::new();
+    let program = deploy(&system);
+
+    let items = vec![(actor(USER), String::new()); 101];
+    let error = call_err(&system, &program, ADMIN, "Nft/BatchMint", (items,));
+    assert!(error.contains("Batch too large: maximum is 100 tokens"), "{error}");
+    let items = Vec::<(ActorId, String)>::new();
+    let error = call_err(&system, &program, ADMIN, "Nft/BatchMint", (items,));
+    assert!(error.contains("Batch cannot be empty"), "{error}");
+
+    // A failing item rolls back the items minted before it
+    let items = vec![(actor(USER), String::new()), (ActorId::zero(), String::new())];
+    let error = call_err(&system, &program, ADMIN, "Nft/BatchMint", (items,));
+    assert!(error.contains("Cannot mint to zero address"), "{error}");
+    let supply: u64 = call(&system, &program, USER, "Nft/TotalSupply", ());
+    assert_eq!(supply, 0);
+    let owner: Option<ActorId> = call(&system, &program, USER, "Nft/OwnerOf", (1u64,));
+    assert_eq!(owner, None);
+
+    // The full batch size is accepted, and the rolled-back IDs are reused
+    let items = vec![(actor(USER), String::new()); 100];
+    let token_ids: Vec<u64> = call(&system, &program, ADMIN, "Nft/BatchMint", (items,));
+    assert_eq!(token_ids, (1..=100).collect::<Vec<u64>>());
+}
+
+// ============================================================================
+// VOUCHER TESTS
+// ============================================================================
+
+/// Mirrors `VoucherTarget`
+#[derive(Clone, Copy, Encode)]
+#[codec(crate = sails_rs::scale_codec)]
+enum VoucherTarget {
+    Token(u64),
+    Collection(u32),
+}
+
+/// Mirrors `MintVoucher`
+#[derive(Clone, Encode)]
+#[codec(crate = sails_rs::scale_codec)]
+struct MintVoucher {
+    recipient: ActorId,
+    target: VoucherTarget,
+    metadata_hash: [u8; 32],
+    expiry: u64,
+    voucher_id: [u8; 32],
+}
+
+/// Backend sr25519 keypair from mini secret [0x01; 32]
+fn backend_keypair() -> schnorrkel::Keypair {
+    schnorrkel::MiniSecretKey::from_bytes(&[0x01; 32])
+        .expect("Invalid mini secret")
+        .expand_to_keypair(schnorrkel::ExpansionMode::Ed25519)
+}
+
+fn voucher(recipient: u64, target: VoucherTarget, metadata_uri: &str, voucher_id: u8) -> MintVoucher {
+    MintVoucher {
+        recipient: actor(recipient),
+        target,
+        metadata_hash: Blake2b::<U32>::digest(metadata_uri.as_bytes()).into(),
+        expiry: u64::MAX,
+        voucher_id: [voucher_id; 32],
+    }
+}
+
+/// Sign a voucher the way the backend does: sr25519 over the blake2b-256
+/// payload hash, under the "substrate" signing context
+fn sign_voucher(voucher: &MintVoucher) -> Vec<u8> {
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update(b"LINE_NFT_VOUCHER_V1");
+    hasher.update(voucher.recipient.as_ref());
+    match voucher.target {
+        VoucherTarget::Token(token_id) => {
+            hasher.update([0u8]);
+            hasher.update(token_id.to_be_bytes());
+        }
+        VoucherTarget::Collection(collection_id) => {
+            hasher.update([1u8]);
+            hasher.update(collection_id.to_be_bytes());
+        }
+    }
+    hasher.update(voucher.metadata_hash);
+    hasher.update(voucher.expiry.to_be_bytes());
+    hasher.update(voucher.voucher_id);
+    let hash: [u8; 32] = hasher.finalize().into();
+
+    let context = schnorrkel::signing_context(b"substrate");
+    backend_keypair().sign(context.bytes(&hash)).to_bytes().to_vec()
+}
+
+fn deploy_with_backend_signer(system: &System) -> Program<'_> {
+    let program = deploy(system);
+    let pubkey = backend_keypair().public.to_bytes();
+    let _: bool = call(system, &program, ADMIN, "Nft/SetBackendSigner", (pubkey,));
+    program
+}
+
+#[test]
+fn test_signed_voucher_mints_once_to_its_recipient() {
+    let system = System::new();
+    let program = deploy_with_backend_signer(&system);
+
+    let voucher = voucher(USER, VoucherTarget::Token(7), "ipfs://reward", 0x01);
+    let redeem = (voucher.clone(), String::from("ipfs://reward"), sign_voucher(&voucher));
+    let token_id: u64 = call(&system, &program, USER, "Nft/RedeemVoucher", redeem.clone());
+    assert_eq!(token_id, 7);
+
+    let owner: Option<ActorId> = call(&system, &program, USER, "Nft/OwnerOf", (7u64,));
+    assert_eq!(owner, Some(actor(USER)));
+    let uri: Option<String> = call(&system, &program, USER, "Nft/TokenUri", (7u64,));
+    assert_eq!(uri.as_deref(), Some("ipfs://reward"));
+    let used: bool = call(&system, &program, USER, "Nft/IsVoucherUsed", ([0x01u8; 32],));
+    assert!(used);
+
+    let error = call_err(&system, &program, USER, "Nft/RedeemVoucher", redeem);
+    assert!(error.contains("Voucher already used"), "{error}");
+}
+
+#[test]
+fn test_invalid_vouchers_are_rejected_without_being_consumed() {
+    let system = System::new();
+    let program = deploy_with_backend_signer(&system);
+    let uri = String::from("ipfs://reward");
+    let voucher = voucher(USER, VoucherTarget::Token(7), &uri, 0x01);
+    let signature = sign_voucher(&voucher);
+
+    // Expired
+    let mut expired = voucher.clone();
+    expired.expiry = 1;
+    let redeem = (expired.clone(), uri.clone(), sign_voucher(&expired));
+    let error = call_err(&system, &program, USER, "Nft/RedeemVoucher", redeem);
+    assert!(error.contains("Voucher expired"), "{error}");
+
+    // Submitted by someone other than the recipient
+    let redeem = (voucher.clone(), uri.clone(), signature.clone());
+    let error = call_err(&system, &program, OTHER, "Nft/RedeemVoucher", redeem);
+    assert!(error.contains("Not voucher recipient"), "{error}");
+
+    // Recipient rewritten to the caller
+    let mut redirected = voucher.clone();
+    redirected.recipient = actor(OTHER);
+    let redeem = (redirected, uri.clone(), signature.clone());
+    let error = call_err(&system, &program, OTHER, "Nft/RedeemVoucher", redeem);
+    assert!(error.contains("Invalid signature"), "{error}");
+
+    // Metadata URI swapped, with and without a matching metadata hash
+    let redeem = (voucher.clone(), String::from("ipfs://legendary"), signature.clone());
+    let error = call_err(&system, &program, USER, "Nft/RedeemVoucher", redeem);
+    assert!(error.contains("Metadata does not match voucher"), "{error}");
+    let mut tampered = voucher.clone();
+    tampered.metadata_hash = Blake2b::<U32>::digest(b"ipfs://legendary").into();
+    let redeem = (tampered, String::from("ipfs://legendary"), signature.clone());
+    let error = call_err(&system, &program, USER, "Nft/RedeemVoucher", redeem);
+    assert!(error.contains("Invalid signature"), "{error}");
+
+    let used: bool = call(&system, &program, USER, "Nft/IsVoucherUsed", ([0x01u8; 32],));
+    assert!(!used);
+    let redeem = (voucher, uri, signature);
+    let token_id: u64 = call(&system, &program, USER, "Nft/RedeemVoucher", redeem);
+    assert_eq!(token_id, 7);
+}
+
+#[test]
+fn test_collection_voucher_respects_max_supply() {
+    let system = System::new();
+    let program = deploy_with_backend_signer(&system);
+    let collection_id = create_collection(&system, &program, Some(1));
+    let target = VoucherTarget::Collection(collection_id);
+
+    let first = voucher(USER, target, "ipfs://first", 0x01);
+    let redeem = (first.clone(), String::from("ipfs://first"), sign_voucher(&first));
+    let token_id: u64 = call(&system, &program, USER, "Nft/RedeemVoucher", redeem);
+    let collection: Option<u32> = call(&system, &program, USER, "Nft/CollectionOf", (token_id,));
+    assert_eq!(collection, Some(collection_id));
+
+    let second = voucher(USER, target, "ipfs://second", 0x02);
+    let redeem = (second.clone(), String::from("ipfs://second"), sign_voucher(&second));
+    let error = call_err(&system, &program, USER, "Nft/RedeemVoucher", redeem);
+    assert!(error.contains("Collection max supply reached"), "{error}");
+
+    // The failed redemption does not burn the voucher
+    let used: bool = call(&system, &program, USER, "Nft/IsVoucherUsed", ([0x02u8; 32],));
+    assert!(!used);
+    let supply: u64 = call(&system, &program, USER, "Nft/TotalSupply", ());
+    assert_eq!(supply, 1);
+}
+
+// ============================================================================
+// SOULBOUND TESTS
+// ============================================================================
+
+#[test]
+fn test_soulbound_token_cannot_move_but_can_be_burned() {
+    let system = System::new();
+    let program = deploy(&system);
+    mint(&system, &program, USER, 1);
+    let _: bool = call(&system, &program, ADMIN, "Nft/SetSoulbound", (1u64, true));
+
+    let transfer = (actor(USER), actor(OTHER), 1u64);
+    let error = call_err(&system, &program, ADMIN, "Nft/TransferFrom", transfer);
+    assert!(error.contains("Token is soulbound"), "{error}");
+    let error = call_err(&system, &program, USER, "Nft/Transfer", (actor(OTHER), 1u64));
+    assert!(error.contains("Token is soulbound"), "{error}");
+
+    let _: bool = call(&system, &program, USER, "Nft/Burn", (1u64,));
+    let owner: Option<ActorId> = call(&system, &program, USER, "Nft/OwnerOf", (1u64,));
+    assert_eq!(owner, None);
+}
+
+// ============================================================================
+// METADATA FREEZE TESTS
+// ============================================================================
+
+#[test]
+fn test_freezing_token_pins_its_uri() {
+    let system = System::new();
+    let program = deploy(&system);
+    mint(&system, &program, USER, 1);
+
+    // Nothing to pin while the token has no explicit or base URI
+    let error = call_err(&system, &program, ADMIN, "Nft/FreezeMetadata", (Some(1u64),));
+    assert!(error.contains("Token has no metadata URI to freeze"), "{error}");
+
+    let _: bool = call(&system, &program, ADMIN, "Nft/SetBaseUri", ("ipfs://v1/".to_string(),));
+    let _: bool = call(&system, &program, ADMIN, "Nft/FreezeMetadata", (Some(1u64),));
+    let _: bool = call(&system, &program, ADMIN, "Nft/SetBaseUri", ("ipfs://v2/".to_string(),));
+
+    let uri: Option<String> = call(&system, &program, USER, "Nft/TokenUri", (1u64,));
+    assert_eq!(uri.as_deref(), Some("ipfs://v1/1"));
+}
+
+// ============================================================================
+// SAFE TRANSFER TESTS
+// ============================================================================
+
+#[test]
+fn test_rejected_safe_transfer_restores_approval_user_and_lock() {
+    let system = System::new();
+    let program = deploy(&system);
+    // Another NFT program has no `NftReceiver` service, so it rejects the token
+    let receiver = deploy_with_id(&system, 200).id();
+
+    mint(&system, &program, USER, 1);
+    let _: bool = call(&system, &program, ADMIN, "Nft/AddLocker", (actor(THIRD),));
+    let _: bool = call(&system, &program, USER, "Nft/Approve", (actor(THIRD), 1u64));
+    let _: bool = call(&system, &program, USER, "Nft/SetUser", (1u64, actor(OTHER), u64::MAX));
+    let _: bool = call(&system, &program, THIRD, "Nft/Lock", (1u64, 7u64));
+
+    let transfer = (actor(USER), receiver, 1u64, Vec::<u8>::new());
+    let accepted: bool = call(&system, &program, THIRD, "Nft/SafeTransferFrom", transfer);
+    assert!(!accepted);
+
+    let owner: Option<ActorId> = call(&system, &program, USER, "Nft/OwnerOf", (1u64,));
+    assert_eq!(owner, Some(actor(USER)));
+    let approved: Option<ActorId> = call(&system, &program, USER, "Nft/GetApproved", (1u64,));
+    assert_eq!(approved, Some(actor(THIRD)));
+    let user: Option<ActorId> = call(&system, &program, USER, "Nft/UserOf", (1u64,));
+    assert_eq!(user, Some(actor(OTHER)));
+    // TokenLock { locker, lock_id }
+    let lock: Option<(ActorId, u64)> = call(&system, &program, USER, "Nft/LockOf", (1u64,));
+    assert_eq!(lock, Some((actor(THIRD), 7)));
+}
+
+#[test]
+fn test_rejected_safe_transfer_restores_nested_child_state() {
+    let system = System::new();
+    let program = deploy(&system);
+    let receiver = deploy_with_id(&system, 200).id();
+
+    mint(&system, &program, USER, 1);
+    mint(&system, &program, USER, 2);
+    let _: bool = call(&system, &program, USER, "Nft/Approve", (actor(THIRD), 2u64));
+    let _: bool = call(&system, &program, USER, "Nft/SetUser", (2u64, actor(OTHER), u64::MAX));
+    let _: bool = call(&system, &program, USER, "Nft/Nest", ((program.id(), 2u64), 1u64));
+
+    let transfer = (actor(USER), receiver, 1u64, Vec::<u8>::new());
+    let accepted: bool = call(&system, &program, USER, "Nft/SafeTransferFrom", transfer);
+    assert!(!accepted);
+
+    let owner: Option<ActorId> = call(&system, &program, USER, "Nft/OwnerOf", (2u64,));
+    assert_eq!(owner, Some(actor(USER)));
+    let approved: Option<ActorId> = call(&system, &program, USER, "Nft/GetApproved", (2u64,));
+    assert_eq!(approved, Some(actor(THIRD)));
+    let user: Option<ActorId> = call(&system, &program, USER, "Nft/UserOf", (2u64,));
+    assert_eq!(user, Some(actor(OTHER)));
+}
+
+#[test]
+fn test_safe_transfer_to_silent_receiver_times_out_and_rolls_back() {
+    let system = System::new();
+    let program = deploy(&system);
+    mint(&system, &program, USER, 1);
+
+    // A user account never replies to `OnNftReceived`
+    let transfer = (actor(USER), actor(THIRD), 1u64, Vec::<u8>::new());
+    let (message_id, result) = send(&system, &program, USER, "Nft/SafeTransferFrom", transfer);
+
+    // The token cannot move, and the admin cannot clear it, during the wait
+    let pending: bool = call(&system, &program, USER, "Nft/IsPending", (1u64,));
+    assert!(pending);
+    let error = call_err(&system, &program, THIRD, "Nft/Transfer", (actor(OTHER), 1u64));
+    assert!(error.contains("Token operation in progress"), "{error}");
+    let error = call_err(&system, &program, ADMIN, "Nft/ClearPending", (1u64,));
+    assert!(error.contains("Token operation in progress"), "{error}");
+
+    let (reply, result) = wait_reply(&system, message_id, result);
+    let payload = reply.expect("SafeTransferFrom failed");
+    let mut cursor = &payload[..];
+    let _route: (String, String) = Decode::decode(&mut cursor).expect("Failed to decode route");
+    assert_eq!(bool::decode(&mut cursor), Ok(false));
+    let rejected: Vec<(ActorId, ActorId, u64)> = events(&result, "Nft/SafeTransferRejected");
+    assert_eq!(rejected, [(actor(USER), actor(THIRD), 1)]);
+
+    let owner: Option<ActorId> = call(&system, &program, USER, "Nft/OwnerOf", (1u64,));
+    assert_eq!(owner, Some(actor(USER)));
+    let pending: bool = call(&system, &program, USER, "Nft/IsPending", (1u64,));
+    assert!(!pending);
+    let error = call_err(&system, &program, ADMIN, "Nft/ClearPending", (1u64,));
+    assert!(error.contains("Token is not pending"), "{error}");
+    let _: bool = call(&system, &program, USER, "Nft/Transfer", (actor(OTHER), 1u64));
+}
+
+// ============================================================================
+// LOCK TESTS
+// ============================================================================
+
+#[test]
+fn test_only_locker_can_move_locked_token() {
+    let system = System::new();
+    let program = deploy(&system);
+    mint(&system, &program, USER, 1);
+    let _: bool = call(&system, &program, ADMIN, "Nft/AddLocker", (actor(THIRD),));
+    let _: bool = call(&system, &program, USER, "Nft/Approve", (actor(THIRD), 1u64));
+    let _: bool = call(&system, &program, THIRD, "Nft/Lock", (1u64, 7u64));
+
+    // Neither the owner nor an admin can move or burn it while locked
+    let error = call_err(&system, &program, USER, "Nft/Transfer", (actor(OTHER), 1u64));
+    assert!(error.contains("Token is locked"), "{error}");
+    let error = call_err(&system, &program, USER, "Vnft/Transfer", (actor(OTHER), U256::from(1)));
+    assert!(error.contains("Token is locked"), "{error}");
+    let error = call_err(&system, &program, ADMIN, "Nft/TransferFrom", (actor(USER), actor(OTHER), 1u64));
+    assert!(error.contains("Token is locked"), "{error}");
+    let error = call_err(&system, &program, USER, "Nft/Burn", (1u64,));
+    assert!(error.contains("Token is locked"), "{error}");
+
+    let owner: Option<ActorId> = call(&system, &program, USER, "Nft/OwnerOf", (1u64,));
+    assert_eq!(owner, Some(actor(USER)));
+
+    // The locker can, and moving the token releases the lock
+    let _: bool = call(&system, &program, THIRD, "Nft/TransferFrom", (actor(USER), actor(OTHER), 1u64));
+    let owner: Option<ActorId> = call(&system, &program, USER, "Nft/OwnerOf", (1u64,));
+    assert_eq!(owner, Some(actor(OTHER)));
+    let lock: Option<(ActorId, u64)> = call(&system, &program, USER, "Nft/LockOf", (1u64,));
+    assert_eq!(lock, None);
+}
+
+// ============================================================================
+// NESTING TESTS
+// ============================================================================
+
+#[test]
+fn test_nested_child_moves_only_with_its_parent() {
+    let system = System::new();
+    let program = deploy(&system);
+    mint(&system, &program, USER, 1);
+    mint(&system, &program, USER, 2);
+
+    // ChildToken { program, token_id }
+    let child = (program.id(), 2u64);
+    let _: bool = call(&system, &program, USER, "Nft/Nest", (child, 1u64));
+
+    let error = call_err(&system, &program, USER, "Nft/Transfer", (actor(OTHER), 2u64));
+    assert!(error.contains("Token is nested"), "{error}");
+    let error = call_err(&system, &program, USER, "Vnft/Transfer", (actor(OTHER), U256::from(2)));
+    assert!(error.contains("Token is nested"), "{error}");
+    let error = call_err(&system, &program, ADMIN, "Nft/TransferFrom", (actor(USER), actor(OTHER), 2u64));
+    assert!(error.contains("Token is nested"), "{error}");
+
+    // Moving the parent carries the child along
+    let (_, result) = send(&system, &program, USER, "Nft/Transfer", (actor(OTHER), 1u64));
+    assert_eq!(
+        vnft_events(&result, "Transfer"),
+        [(actor(USER), actor(OTHER), U256::from(1)), (actor(USER), actor(OTHER), U256::from(2))]
+    );
+    let owner: Option<ActorId> = call(&system, &program, USER, "Nft/OwnerOf", (2u64,));
+    assert_eq!(owner, Some(actor(OTHER)));
+    let parent: Option<u64> = call(&system, &program, USER, "Nft/ParentOf", (2u64,));
+    assert_eq!(parent, Some(1));
+    let children: Vec<(ActorId, u64)> = call(&system, &program, USER, "Nft/ChildrenOf", (1u64,));
+    assert_eq!(children, [child]);
+
+    // Once unnested the child moves on its own again
+    let _: bool = call(&system, &program, OTHER, "Nft/Unnest", (child,));
+    let _: bool = call(&system, &program, OTHER, "Nft/Transfer", (actor(THIRD), 2u64));
+    let owner: Option<ActorId> = call(&system, &program, USER, "Nft/OwnerOf", (1u64,));
+    assert_eq!(owner, Some(actor(OTHER)));
+    let owner: Option<ActorId> = call(&system, &program, USER, "Nft/OwnerOf", (2u64,));
+    assert_eq!(owner, Some(actor(THIRD)));
+}
+
+#[test]
+fn test_remote_refusals_leave_nesting_unchanged() {
+    let system = System::new();
+    let program = deploy(&system);
+    let remote = deploy_with_id(&system, 200);
+    let _: bool = call(&system, &program, ADMIN, "Nft/AddNestableProgram", (remote.id(),));
+    mint(&system, &program, USER, 1);
+    mint(&system, &remote, USER, 5);
+    let child = (remote.id(), 5u64);
+
+    // Escrow fails while this program is not approved for the child
+    let nested: bool = call(&system, &program, USER, "Nft/Nest", (child, 1u64));
+    assert!(!nested);
+    let children: Vec<(ActorId, u64)> = call(&system, &program, USER, "Nft/ChildrenOf", (1u64,));
+    assert_eq!(children, []);
+    let pending: bool = call(&system, &program, USER, "Nft/IsPending", (1u64,));
+    assert!(!pending);
+
+    let _: bool = call(&system, &remote, USER, "Nft/Approve", (program.id(), 5u64));
+    let nested: bool = call(&system, &program, USER, "Nft/Nest", (child, 1u64));
+    assert!(nested);
+    let owner: Option<ActorId> = call(&system, &remote, USER, "Nft/OwnerOf", (5u64,));
+    assert_eq!(owner, Some(program.id()));
+
+    // Release fails while the remote keeps the child soulbound
+    let _: bool = call(&system, &remote, ADMIN, "Nft/SetSoulbound", (5u64, true));
+    let unnested: bool = call(&system, &program, USER, "Nft/Unnest", (child,));
+    assert!(!unnested);
+    let children: Vec<(ActorId, u64)> = call(&system, &program, USER, "Nft/ChildrenOf", (1u64,));
+    assert_eq!(children, [child]);
+    let pending: bool = call(&system, &program, USER, "Nft/IsPending", (1u64,));
+    assert!(!pending);
+
+    let _: bool = call(&system, &remote, ADMIN, "Nft/SetSoulbound", (5u64, false));
+    let unnested: bool = call(&system, &program, USER, "Nft/Unnest", (child,));
+    assert!(unnested);
+    let owner: Option<ActorId> = call(&system, &remote, USER, "Nft/OwnerOf", (5u64,));
+    assert_eq!(owner, Some(actor(USER)));
+}
+
+#[test]
+fn test_nest_with_silent_remote_times_out_and_releases_parent() {
+    let system = System::new();
+    let program = deploy(&system);
+    // A user account never replies to `Nft/TransferFrom`
+    let _: bool = call(&system, &program, ADMIN, "Nft/AddNestableProgram", (actor(THIRD),));
+    mint(&system, &program, USER, 1);
+    let child = (actor(THIRD), 5u64);
+
+    let (message_id, result) = send(&system, &program, USER, "Nft/Nest", (child, 1u64));
+    let error = call_err(&system, &program, USER, "Nft/Transfer", (actor(OTHER), 1u64));
+    assert!(error.contains("Token operation in progress"), "{error}");
+
+    let (reply, _) = wait_reply(&system, message_id, result);
+    let payload = reply.expect("Nest failed");
+    let mut cursor = &payload[..];
+    let _route: (String, String) = Decode::decode(&mut cursor).expect("Failed to decode route");
+    assert_eq!(bool::decode(&mut cursor), Ok(false));
+
+    let children: Vec<(ActorId, u64)> = call(&system, &program, USER, "Nft/ChildrenOf", (1u64,));
+    assert_eq!(children, []);
+    let _: bool = call(&system, &program, USER, "Nft/Transfer", (actor(OTHER), 1u64));
+}
+
+// ============================================================================
+// HIDDEN BATCH TESTS
+// ============================================================================
+
+#[test]
+fn test_reveal_applies_offset_fixed_at_close() {
+    let system = System::new();
+    let program = deploy(&system);
+
+    let salt = [9u8; 32];
+    let uris = vec![String::from("ipfs://a"), String::from("ipfs://b"), String::from("ipfs://c")];
+    let commitment: [u8; 32] = Blake2b::<U32>::digest((salt, &uris).encode()).into();
+    let batch = (String::from("ipfs://hidden"), commitment, 3u32);
+    let batch_id: u64 = call(&system, &program, ADMIN, "Nft/CreateHiddenBatch", batch);
+    let first: u64 = call(&system, &program, ADMIN, "Nft/MintHidden", (batch_id, actor(USER)));
+    let second: u64 = call(&system, &program, ADMIN, "Nft/MintHidden", (batch_id, actor(OTHER)));
+
+    // RevealData { salt, uris }
+    let reveal = (batch_id, (salt, uris.clone()));
+    let error = call_err(&system, &program, ADMIN, "Nft/Reveal", reveal.clone());
+    assert!(error.contains("Batch is not closed"), "{error}");
+
+    let offset: u32 = call(&system, &program, ADMIN, "Nft/CloseHiddenBatch", (batch_id,));
+    assert!(offset < 3);
+    let error = call_err(&system, &program, ADMIN, "Nft/MintHidden", (batch_id, actor(THIRD)));
+    assert!(error.contains("Batch is closed"), "{error}");
+    let error = call_err(&system, &program, ADMIN, "Nft/CloseHiddenBatch", (batch_id,));
+    assert!(error.contains("Batch already closed"), "{error}");
+
+    let (_, result) = send(&system, &program, ADMIN, "Nft/Reveal", reveal);
+    // MetadataScope::Token(id)
+    let updated: Vec<(u8, u64)> = events(&result, "Nft/MetadataUpdated");
+    assert_eq!(updated, [(0, first), (0, second)]);
+
+    for (index, token_id) in [first, second].into_iter().enumerate() {
+        let uri: Option<String> = call(&system, &program, USER, "Nft/TokenUri", (token_id,));
+        assert_eq!(uri.as_ref(), Some(&uris[(index + offset as usize) % 3]));
+    }
+}
+
+// ============================================================================
+// MULTI-TOKEN TESTS
+// ============================================================================
+
+#[test]
+fn test_multi_token_operator_does_not_control_nfts() {
+    let system = System::new();
+    let program = deploy(&system);
+    mint(&system, &program, USER, 1);
+    let edition: u64 =
+        call(&system, &program, ADMIN, "MultiToken/CreateEdition", (None::<u64>, String::new()));
+    let _: bool = call(&system, &program, ADMIN, "MultiToken/Mint", (actor(USER), edition, 5u64));
+
+    let approval = (actor(OTHER), true);
+    let _: bool = call(&system, &program, USER, "MultiToken/SetApprovalForAll", approval);
+    let query = (actor(USER), actor(OTHER));
+    let approved: bool = call(&system, &program, USER, "Nft/IsApprovedForAll", query);
+    assert!(!approved);
+
+    let transfer = (actor(USER), actor(OTHER), 1u64);
+    let error = call_err(&system, &program, OTHER, "Nft/TransferFrom", transfer);
+    assert!(error.contains("Not authorized"), "{error}");
+
+    let transfer = (actor(USER), actor(OTHER), edition, 2u64);
+    let _: bool = call(&system, &program, OTHER, "MultiToken/TransferFrom", transfer);
+    let query = (actor(OTHER), edition);
+    let balance: u64 = call(&system, &program, USER, "MultiToken/BalanceOf", query);
+    assert_eq!(balance, 2);
+}
+
+#[test]
+fn test_multi_token_rejects_zero_amount_transfers() {
+    let system = System::new();
+    let program = deploy(&system);
+    let edition: u64 =
+        call(&system, &program, ADMIN, "MultiToken/CreateEdition", (None::<u64>, String::new()));
+
+    let transfer = (actor(USER), actor(OTHER), edition, 0u64);
+    let error = call_err(&system, &program, USER, "MultiToken/TransferFrom", transfer);
+    assert!(error.contains("Amount must be greater than zero"), "{error}");
+
+    let transfer = (actor(USER), actor(OTHER), vec![edition], vec![0u64]);
+    let error = call_err(&system, &program, USER, "MultiToken/BatchTransferFrom", transfer);
+    assert!(error.contains("Amount must be greater than zero"), "{error}");
+}
+
+// ============================================================================
+// RENTAL TESTS
+// ============================================================================
+
+#[test]
+fn test_user_is_cleared_on_transfer() {
+    let system = System::new();
+    let program = deploy(&system);
+    mint(&system, &program, USER, 1);
+
+    let _: bool = call(&system, &program, USER, "Nft/SetUser", (1u64, actor(THIRD), u64::MAX));
+    let user: Option<ActorId> = call(&system, &program, USER, "Nft/UserOf", (1u64,));
+    assert_eq!(user, Some(actor(THIRD)));
+
+    let _: bool = call(&system, &program, USER, "Nft/Transfer", (actor(OTHER), 1u64));
+
+    let user: Option<ActorId> = call(&system, &program, USER, "Nft/UserOf", (1u64,));
+    assert_eq!(user, None);
+    let expires: Option<u64> = call(&system, &program, USER, "Nft/UserExpires", (1u64,));
+    assert_eq!(expires, None);
+}
+
+// ============================================================================
+// STANDARD EVENT TESTS
+// ============================================================================
+
+#[test]
+fn test_nft_paths_emit_standard_vnft_events() {
+    let system = System::new();
+    let program = deploy(&system);
+
+    let (_, result) = send(&system, &program, ADMIN, "Nft/Mint", (actor(USER), 1u64, String::new()));
+    assert_eq!(vnft_events(&result, "Transfer"), [(ActorId::zero(), actor(USER), U256::from(1))]);
+
+    let (_, result) = send(&system, &program, USER, "Nft/Approve", (actor(OTHER), 1u64));
+    assert_eq!(vnft_events(&result, "Approval"), [(actor(USER), actor(OTHER), U256::from(1))]);
+
+    let (_, result) = send(&system, &program, OTHER, "Nft/TransferFrom", (actor(USER), actor(THIRD), 1u64));
+    assert_eq!(vnft_events(&result, "Transfer"), [(actor(USER), actor(THIRD), U256::from(1))]);
+
+    let (_, result) = send(&system, &program, THIRD, "Nft/Burn", (1u64,));
+    assert_eq!(vnft_events(&result, "Transfer"), [(actor(THIRD), ActorId::zero(), U256::from(1))]);
+}
+
+#[test]
+fn test_vnft_transfer_emits_single_standard_event() {
+    let system = System::new();
+    let program = deploy(&system);
+    mint(&system, &program, USER, 1);
+
+    let (_, result) = send(&system, &program, USER, "Vnft/Transfer", (actor(OTHER), U256::from(1)));
+    assert_eq!(vnft_events(&result, "Transfer"), [(actor(USER), actor(OTHER), U256::from(1))]);
+}
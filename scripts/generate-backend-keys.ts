@@ -8,7 +8,7 @@
  * Output:
  * - seed: 32-byte hex for LINE_BACKEND_SIGNER_SEED env var (KEEP SECRET!)
  * - publicKey: 32-byte hex for reference
- * - publicKeyRustArray: [u8; 32] literal for SetBackendSigner contract call
+ * - signerKey: `{ Sr25519: [...] }` SignerKey argument for SetBackendSigner contract call
  */
 
 import { cryptoWaitReady, sr25519PairFromSeed, randomAsU8a } from '@polkadot/util-crypto';
@@ -24,9 +24,9 @@ async function generateBackendKeys() {
     // Derive sr25519 keypair from seed
     const keypair = sr25519PairFromSeed(seed);
 
-    // Format public key as Rust [u8; 32] array literal
+    // Format public key as the SignerKey argument of SetBackendSigner
     const pubkeyBytes = Array.from(keypair.publicKey);
-    const rustArray = `[${pubkeyBytes.join(', ')}]`;
+    const signerKey = `{ Sr25519: [${pubkeyBytes.join(', ')}] }`;
 
     console.log('\n' + '='.repeat(70));
     console.log('LINE TOKEN BACKEND SIGNER KEYS');
@@ -40,15 +40,15 @@ async function generateBackendKeys() {
     console.log('\n2. PUBLIC KEY (hex, for reference):');
     console.log(`   ${u8aToHex(keypair.publicKey)}`);
 
-    console.log('\n3. PUBLIC KEY as [u8; 32] (for SetBackendSigner contract call):');
-    console.log(`   ${rustArray}`);
+    console.log('\n3. SIGNER KEY (SignerKey argument for SetBackendSigner contract call):');
+    console.log(`   ${signerKey}`);
 
     console.log('\n' + '='.repeat(70));
     console.log('NEXT STEPS:');
     console.log('='.repeat(70));
     console.log('1. Add to .env:');
     console.log(`   LINE_BACKEND_SIGNER_SEED=${u8aToHex(seed)}`);
-    console.log('\n2. Call SetBackendSigner on the contract with the [u8; 32] array above');
+    console.log('\n2. Call SetBackendSigner on the contract with the SignerKey above');
     console.log('='.repeat(70) + '\n');
 }
 